@@ -0,0 +1,224 @@
+use lazy_static::lazy_static;
+
+use crate::static_graphql::schema::{self, DirectiveDefinition, TypeDefinition};
+
+use super::TypeDefinitionExtension;
+
+/// The scalars and directives specified by GraphQL. Schemas exported by servers usually don't
+/// define them, so schema lookups fall back to these definitions.
+static BUILTINS_SDL: &str = r#"
+scalar Int
+scalar Float
+scalar String
+scalar Boolean
+scalar ID
+
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @deprecated(
+  reason: String = "No longer supported"
+) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+directive @specifiedBy(url: String!) on SCALAR
+directive @oneOf on INPUT_OBJECT
+"#;
+
+/// The introspection types, and the meta-fields selecting them (defined by `__MetaFields`, which
+/// is not a type of the schema).
+static INTROSPECTION_SDL: &str = r#"
+type __MetaFields {
+  __typename: String!
+  __schema: __Schema!
+  __type(name: String!): __Type
+}
+
+type __Schema {
+  description: String
+  types: [__Type!]!
+  queryType: __Type!
+  mutationType: __Type
+  subscriptionType: __Type
+  directives: [__Directive!]!
+}
+
+type __Type {
+  kind: __TypeKind!
+  name: String
+  description: String
+  specifiedByURL: String
+  fields(includeDeprecated: Boolean = false): [__Field!]
+  interfaces: [__Type!]
+  possibleTypes: [__Type!]
+  enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+  inputFields(includeDeprecated: Boolean = false): [__InputValue!]
+  ofType: __Type
+  isOneOf: Boolean
+}
+
+enum __TypeKind {
+  SCALAR
+  OBJECT
+  INTERFACE
+  UNION
+  ENUM
+  INPUT_OBJECT
+  LIST
+  NON_NULL
+}
+
+type __Field {
+  name: String!
+  description: String
+  args(includeDeprecated: Boolean = false): [__InputValue!]!
+  type: __Type!
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __InputValue {
+  name: String!
+  description: String
+  type: __Type!
+  defaultValue: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __EnumValue {
+  name: String!
+  description: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __Directive {
+  name: String!
+  description: String
+  isRepeatable: Boolean!
+  locations: [__DirectiveLocation!]!
+  args(includeDeprecated: Boolean = false): [__InputValue!]!
+}
+
+enum __DirectiveLocation {
+  QUERY
+  MUTATION
+  SUBSCRIPTION
+  FIELD
+  FRAGMENT_DEFINITION
+  FRAGMENT_SPREAD
+  INLINE_FRAGMENT
+  VARIABLE_DEFINITION
+  SCHEMA
+  SCALAR
+  OBJECT
+  FIELD_DEFINITION
+  ARGUMENT_DEFINITION
+  INTERFACE
+  UNION
+  ENUM
+  ENUM_VALUE
+  INPUT_OBJECT
+  INPUT_FIELD_DEFINITION
+}
+"#;
+
+const META_FIELDS_TYPE_NAME: &str = "__MetaFields";
+
+lazy_static! {
+    static ref BUILTINS: schema::Document = crate::parser::parse_schema::<String>(BUILTINS_SDL)
+        .expect("built-in definitions should be valid")
+        .into_static();
+    static ref INTROSPECTION: schema::Document =
+        crate::parser::parse_schema::<String>(INTROSPECTION_SDL)
+            .expect("introspection definitions should be valid")
+            .into_static();
+}
+
+/// The built-in scalar types: `Int`, `Float`, `String`, `Boolean` and `ID`.
+pub fn builtin_types() -> impl Iterator<Item = &'static TypeDefinition> {
+    BUILTINS
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            schema::Definition::TypeDefinition(type_def) => Some(type_def),
+            _ => None,
+        })
+}
+
+/// The built-in directives: `@skip`, `@include`, `@deprecated`, `@specifiedBy` and `@oneOf`.
+pub fn builtin_directives() -> impl Iterator<Item = &'static DirectiveDefinition> {
+    BUILTINS
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            schema::Definition::DirectiveDefinition(directive_def) => Some(directive_def),
+            _ => None,
+        })
+}
+
+pub fn builtin_type_by_name(name: &str) -> Option<&'static TypeDefinition> {
+    builtin_types().find(|type_def| type_def.name() == name)
+}
+
+pub fn builtin_directive_by_name(name: &str) -> Option<&'static DirectiveDefinition> {
+    builtin_directives().find(|directive_def| directive_def.name == name)
+}
+
+/// The introspection types: `__Schema`, `__Type`, `__TypeKind`, `__Field`, `__InputValue`,
+/// `__EnumValue`, `__Directive` and `__DirectiveLocation`.
+pub fn introspection_types() -> impl Iterator<Item = &'static TypeDefinition> {
+    INTROSPECTION
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            schema::Definition::TypeDefinition(type_def)
+                if type_def.name() != META_FIELDS_TYPE_NAME =>
+            {
+                Some(type_def)
+            }
+            _ => None,
+        })
+}
+
+pub fn introspection_type_by_name(name: &str) -> Option<&'static TypeDefinition> {
+    introspection_types().find(|type_def| type_def.name() == name)
+}
+
+/// The definition of `__typename`, `__schema` or `__type`. Only `__typename` is available on
+/// every composite type, the others are fields of the query type.
+pub fn meta_field_by_name(name: &str) -> Option<&'static schema::Field> {
+    INTROSPECTION
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            schema::Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.name == META_FIELDS_TYPE_NAME =>
+            {
+                object.fields.iter().find(|field| field.name == name)
+            }
+            _ => None,
+        })
+}
+
+#[test]
+fn builtin_definitions() {
+    assert_eq!(builtin_types().count(), 5);
+    assert!(builtin_type_by_name("Boolean").is_some_and(|t| t.is_scalar_type()));
+    assert!(builtin_type_by_name("Query").is_none());
+    assert_eq!(
+        builtin_directives()
+            .map(|d| d.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["skip", "include", "deprecated", "specifiedBy", "oneOf"]
+    );
+}
+
+#[test]
+fn introspection_definitions() {
+    assert_eq!(introspection_types().count(), 8);
+    assert!(introspection_type_by_name(META_FIELDS_TYPE_NAME).is_none());
+    assert_eq!(
+        meta_field_by_name("__type").map(|field| field.arguments.len()),
+        Some(1)
+    );
+    assert!(meta_field_by_name("__nothing").is_none());
+}
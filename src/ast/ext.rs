@@ -2,6 +2,11 @@ use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 
+use super::builtins::{
+    builtin_directive_by_name, builtin_type_by_name, builtin_types, introspection_type_by_name,
+};
+use crate::parser::Pos;
+
 use crate::static_graphql::query::{
     self, Directive, FragmentSpread, OperationDefinition, SelectionSet, Type, Value,
     VariableDefinition,
@@ -42,6 +47,7 @@ pub trait OperationDefinitionExtension {
     fn variable_definitions(&self) -> &[VariableDefinition];
     fn directives(&self) -> &[Directive];
     fn selection_set(&self) -> &SelectionSet;
+    fn position(&self) -> Pos;
 }
 
 impl OperationDefinitionExtension for OperationDefinition {
@@ -71,78 +77,42 @@ impl OperationDefinitionExtension for OperationDefinition {
             OperationDefinition::Subscription(subscription) => &subscription.directives,
         }
     }
+
+    fn position(&self) -> Pos {
+        match self {
+            OperationDefinition::Query(query) => query.position,
+            OperationDefinition::SelectionSet(selection_set) => selection_set.span.0,
+            OperationDefinition::Mutation(mutation) => mutation.position,
+            OperationDefinition::Subscription(subscription) => subscription.position,
+        }
+    }
 }
 
 pub trait SchemaDocumentExtension {
     fn type_by_name(&self, name: &str) -> Option<&TypeDefinition>;
     fn type_map(&self) -> HashMap<&str, &TypeDefinition>;
     fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition>;
-    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType>;
     fn schema_definition(&self) -> &schema::SchemaDefinition;
-    fn query_type(&self) -> &ObjectType;
-    fn mutation_type(&self) -> Option<&ObjectType>;
-    fn subscription_type(&self) -> Option<&ObjectType>;
-    fn is_subtype(&self, sub_type: &Type, super_type: &Type) -> bool;
-    fn is_named_subtype(&self, sub_type_name: &str, super_type_name: &str) -> bool;
     fn is_possible_type(
         &self,
         abstract_type: &TypeDefinition,
         possible_type: &TypeDefinition,
     ) -> bool;
-}
 
-impl SchemaDocumentExtension for schema::Document {
-    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
-        for def in &self.definitions {
-            if let schema::Definition::TypeDefinition(type_def) = def {
-                if type_def.name().eq(name) {
-                    return Some(type_def);
-                }
-            }
-        }
-
-        None
-    }
-
-    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
-        for def in &self.definitions {
-            if let schema::Definition::DirectiveDefinition(directive_def) = def {
-                if directive_def.name.eq(name) {
-                    return Some(directive_def);
-                }
-            }
+    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType> {
+        match self.type_by_name(name) {
+            Some(TypeDefinition::Object(object_def)) => Some(object_def),
+            _ => None,
         }
-
-        None
     }
 
-    fn schema_definition(&self) -> &schema::SchemaDefinition {
-        lazy_static! {
-            static ref DEFAULT_SCHEMA_DEF: schema::SchemaDefinition = {
-                schema::SchemaDefinition {
-                    query: Some("Query".to_string()),
-                    ..Default::default()
-                }
-            };
-        }
-        self.definitions
-            .iter()
-            .find_map(|definition| match definition {
-                schema::Definition::SchemaDefinition(schema_definition) => Some(schema_definition),
-                _ => None,
-            })
-            .unwrap_or(&*DEFAULT_SCHEMA_DEF)
-    }
-
-    fn query_type(&self) -> &ObjectType {
-        lazy_static! {
-            static ref QUERY: String = "Query".to_string();
-        }
-
-        let schema_definition = self.schema_definition();
-
-        self.object_type_by_name(schema_definition.query.as_ref().unwrap_or(&QUERY))
-            .unwrap()
+    /// The root types are the ones of the schema definition or, when the schema has none, the
+    /// object types named `Query`, `Mutation` and `Subscription`.
+    fn query_type(&self) -> Option<&ObjectType> {
+        self.schema_definition()
+            .query
+            .as_ref()
+            .and_then(|name| self.object_type_by_name(name))
     }
 
     fn mutation_type(&self) -> Option<&ObjectType> {
@@ -159,23 +129,15 @@ impl SchemaDocumentExtension for schema::Document {
             .and_then(|name| self.object_type_by_name(name))
     }
 
-    fn object_type_by_name(&self, name: &str) -> Option<&ObjectType> {
-        match self.type_by_name(name) {
-            Some(TypeDefinition::Object(object_def)) => Some(object_def),
-            _ => None,
-        }
-    }
-
-    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
-        let mut type_map = HashMap::new();
-
-        for def in &self.definitions {
-            if let schema::Definition::TypeDefinition(type_def) = def {
-                type_map.insert(type_def.name(), type_def);
+    /// The root type of an operation, `None` when the schema doesn't support its operation type.
+    fn root_operation_type(&self, operation: &OperationDefinition) -> Option<&ObjectType> {
+        match operation {
+            OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_) => {
+                self.query_type()
             }
+            OperationDefinition::Mutation(_) => self.mutation_type(),
+            OperationDefinition::Subscription(_) => self.subscription_type(),
         }
-
-        type_map
     }
 
     fn is_named_subtype(&self, sub_type_name: &str, super_type_name: &str) -> bool {
@@ -191,27 +153,6 @@ impl SchemaDocumentExtension for schema::Document {
         }
     }
 
-    fn is_possible_type(
-        &self,
-        abstract_type: &TypeDefinition,
-        possible_type: &TypeDefinition,
-    ) -> bool {
-        match abstract_type {
-            TypeDefinition::Union(union_typedef) => {
-                return union_typedef
-                    .types
-                    .iter()
-                    .any(|t| t == possible_type.name());
-            }
-            TypeDefinition::Interface(interface_typedef) => {
-                let implementes_interfaces = possible_type.interfaces();
-
-                implementes_interfaces.contains(&interface_typedef.name)
-            }
-            _ => false,
-        }
-    }
-
     fn is_subtype(&self, sub_type: &Type, super_type: &Type) -> bool {
         // Equivalent type is a valid subtype
         if sub_type == super_type {
@@ -260,6 +201,96 @@ impl SchemaDocumentExtension for schema::Document {
     }
 }
 
+/// Lookups fall back to the built-in scalars and directives (see `builtin_types`), and to the
+/// introspection types, when the document doesn't define them. Raw documents always fall back:
+/// to only resolve the types and directives a schema defines, look them up through
+/// `PreparedSchema::new(&document).without_builtins()` instead.
+///
+/// Type extensions are not merged: lookups only see the type definitions, without the fields,
+/// members, values or directives added by `extend` definitions. Use `PreparedSchema` (or
+/// `merge_type_extensions`) for schemas with type extensions.
+impl SchemaDocumentExtension for schema::Document {
+    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
+        for def in &self.definitions {
+            if let schema::Definition::TypeDefinition(type_def) = def {
+                if type_def.name().eq(name) {
+                    return Some(type_def);
+                }
+            }
+        }
+
+        builtin_type_by_name(name).or_else(|| introspection_type_by_name(name))
+    }
+
+    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
+        for def in &self.definitions {
+            if let schema::Definition::DirectiveDefinition(directive_def) = def {
+                if directive_def.name.eq(name) {
+                    return Some(directive_def);
+                }
+            }
+        }
+
+        builtin_directive_by_name(name)
+    }
+
+    fn schema_definition(&self) -> &schema::SchemaDefinition {
+        self.definitions
+            .iter()
+            .find_map(|definition| match definition {
+                schema::Definition::SchemaDefinition(schema_definition) => Some(schema_definition),
+                _ => None,
+            })
+            .unwrap_or(&*DEFAULT_SCHEMA_DEFINITION)
+    }
+
+    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
+        let mut type_map: HashMap<&str, &TypeDefinition> = builtin_types()
+            .map(|type_def| (type_def.name(), type_def))
+            .collect();
+
+        for def in &self.definitions {
+            if let schema::Definition::TypeDefinition(type_def) = def {
+                type_map.insert(type_def.name(), type_def);
+            }
+        }
+
+        type_map
+    }
+
+    fn is_possible_type(
+        &self,
+        abstract_type: &TypeDefinition,
+        possible_type: &TypeDefinition,
+    ) -> bool {
+        match abstract_type {
+            TypeDefinition::Union(union_typedef) => {
+                return union_typedef
+                    .types
+                    .iter()
+                    .any(|t| t == possible_type.name());
+            }
+            TypeDefinition::Interface(interface_typedef) => {
+                let implementes_interfaces = possible_type.interfaces();
+
+                implementes_interfaces.contains(&interface_typedef.name)
+            }
+            _ => false,
+        }
+    }
+}
+
+lazy_static! {
+    /// The root types of schemas without a schema definition.
+    pub(crate) static ref DEFAULT_SCHEMA_DEFINITION: schema::SchemaDefinition =
+        schema::SchemaDefinition {
+            query: Some("Query".to_string()),
+            mutation: Some("Mutation".to_string()),
+            subscription: Some("Subscription".to_string()),
+            ..Default::default()
+        };
+}
+
 pub trait TypeExtension {
     fn inner_type(&self) -> &str;
     fn is_non_null(&self) -> bool;
@@ -373,6 +404,8 @@ pub trait TypeDefinitionExtension {
     fn is_enum_type(&self) -> bool;
     fn is_scalar_type(&self) -> bool;
     fn is_abstract_type(&self) -> bool;
+    /// Whether this is an input object type with the `@oneOf` directive.
+    fn is_one_of_input_type(&self) -> bool;
     fn name(&self) -> &str;
 }
 
@@ -559,6 +592,13 @@ impl TypeDefinitionExtension for Option<&schema::TypeDefinition> {
         }
     }
 
+    fn is_one_of_input_type(&self) -> bool {
+        match self {
+            Some(t) => t.is_one_of_input_type(),
+            _ => false,
+        }
+    }
+
     fn name(&self) -> &str {
         match self {
             Some(t) => t.name(),
@@ -635,6 +675,15 @@ impl TypeDefinitionExtension for schema::TypeDefinition {
     fn is_scalar_type(&self) -> bool {
         matches!(self, schema::TypeDefinition::Scalar(_o))
     }
+
+    fn is_one_of_input_type(&self) -> bool {
+        match self {
+            schema::TypeDefinition::InputObject(i) => {
+                i.directives.iter().any(|d| d.name == "oneOf")
+            }
+            _ => false,
+        }
+    }
 }
 
 pub trait AstNodeWithName {
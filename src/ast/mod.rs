@@ -1,12 +1,18 @@
+pub mod builtins;
 pub mod collect_fields;
 pub mod ext;
 pub mod operation_transformer;
 pub mod operation_visitor;
+pub mod prepared_schema;
 /// Utilities visiting GraphQL AST trees
 pub mod schema_visitor;
+pub mod type_extensions;
 
+pub use self::builtins::*;
 pub use self::collect_fields::*;
 pub use self::ext::*;
 pub use self::operation_transformer::*;
 pub use self::operation_visitor::*;
+pub use self::prepared_schema::*;
 pub use self::schema_visitor::*;
+pub use self::type_extensions::*;
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
 use crate::parser::query::TypeCondition;
+use crate::parser::Pos;
 
 use crate::static_graphql::{
     query::{self, *},
@@ -8,14 +9,14 @@ use crate::static_graphql::{
 };
 
 use super::{
-    FieldByNameExtension, OperationDefinitionExtension, SchemaDocumentExtension, TypeExtension,
+    OperationDefinitionExtension, PreparedSchema, SchemaDocumentExtension, TypeDefinitionExtension,
+    TypeExtension,
 };
 /// OperationVisitor
 pub struct OperationVisitorContext<'a> {
-    pub schema: &'a schema::Document,
+    pub schema: &'a PreparedSchema<'a>,
     pub operation: &'a query::Document,
     pub known_fragments: HashMap<&'a str, &'a FragmentDefinition>,
-    pub directives: HashMap<String, schema::DirectiveDefinition>,
 
     type_stack: Vec<Option<&'a schema::TypeDefinition>>,
     parent_type_stack: Vec<Option<&'a schema::TypeDefinition>>,
@@ -23,10 +24,12 @@ pub struct OperationVisitorContext<'a> {
     type_literal_stack: Vec<Option<Type>>,
     input_type_literal_stack: Vec<Option<&'a Type>>,
     field_stack: Vec<Option<&'a schema::Field>>,
+    position_stack: Vec<Pos>,
+    aborted: bool,
 }
 
 impl<'a> OperationVisitorContext<'a> {
-    pub fn new(operation: &'a Document, schema: &'a schema::Document) -> Self {
+    pub fn new(operation: &'a Document, schema: &'a PreparedSchema<'a>) -> Self {
         OperationVisitorContext {
             schema,
             operation,
@@ -36,20 +39,14 @@ impl<'a> OperationVisitorContext<'a> {
             type_literal_stack: vec![],
             input_type_literal_stack: vec![],
             field_stack: vec![],
+            position_stack: vec![],
+            aborted: false,
             known_fragments: HashMap::from_iter(operation.definitions.iter().filter_map(|def| {
                 match def {
                     Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
                     _ => None,
                 }
             })),
-            directives: HashMap::<String, schema::DirectiveDefinition>::from_iter(
-                schema.definitions.iter().filter_map(|def| match def {
-                    schema::Definition::DirectiveDefinition(directive_def) => {
-                        Some((directive_def.name.clone(), directive_def.clone()))
-                    }
-                    _ => None,
-                }),
-            ),
         }
     }
 
@@ -112,6 +109,16 @@ impl<'a> OperationVisitorContext<'a> {
         self.input_type_stack.pop();
     }
 
+    /// Runs `func` with `position` as the position of the innermost node being visited.
+    pub fn with_position<Func>(&mut self, position: Pos, func: Func)
+    where
+        Func: FnOnce(&mut OperationVisitorContext<'a>),
+    {
+        self.position_stack.push(position);
+        func(self);
+        self.position_stack.pop();
+    }
+
     pub fn current_type(&self) -> Option<&schema::TypeDefinition> {
         self.type_stack.last().unwrap_or(&None).as_deref()
     }
@@ -135,6 +142,24 @@ impl<'a> OperationVisitorContext<'a> {
     pub fn current_field(&self) -> Option<&schema::Field> {
         self.field_stack.last().unwrap_or(&None).as_deref()
     }
+
+    /// Position of the innermost visited node that has one.
+    ///
+    /// Arguments and values don't carry a position in the AST, so while visiting them this is
+    /// the position of the field, directive or variable definition they belong to.
+    pub fn current_position(&self) -> Option<Pos> {
+        self.position_stack.last().copied()
+    }
+
+    /// Stops the traversal: the nodes that were not entered yet are skipped, while the nodes
+    /// being visited are still left.
+    pub fn abort(&mut self) {
+        self.aborted = true;
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.aborted
+    }
 }
 
 pub fn visit_document<'a, Visitor, UserContext>(
@@ -143,7 +168,7 @@ pub fn visit_document<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     visitor.enter_document(context, user_context, document);
     visit_definitions(visitor, &document.definitions, context, user_context);
@@ -156,56 +181,22 @@ fn visit_definitions<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for definition in definitions {
+        if context.is_aborted() {
+            break;
+        }
+
         let schema_type_name = match definition {
             Definition::Fragment(fragment) => {
                 let TypeCondition::On(name) = &fragment.type_condition;
                 Some(name)
             }
-            Definition::Operation(operation) => match operation {
-                OperationDefinition::Query(_) => Some(&context.schema.query_type().name),
-                OperationDefinition::SelectionSet(_) => Some(&context.schema.query_type().name),
-                OperationDefinition::Mutation(_) => {
-                    context.schema.mutation_type().map(|t| &t.name).or_else(|| {
-                        // Awkward hack but enables me to move forward
-                        // Somehow the `mutation_type()` gives None, even though `Mutation` type is defined in the schema.
-                        if let Some(type_definition) = context.schema.type_by_name("Mutation") {
-                            return match type_definition {
-                                crate::parser::schema::TypeDefinition::Object(object_type) => {
-                                    Some(&object_type.name)
-                                }
-                                _ => None,
-                            };
-                        }
-
-                        None
-                    })
-                }
-                OperationDefinition::Subscription(_) => {
-                    context
-                        .schema
-                        .subscription_type()
-                        .map(|t| &t.name)
-                        .or_else(|| {
-                            // Awkward hack but enables me to move forward
-                            // Somehow the `subscription_type()` gives None, even though `Subscription` type is defined in the schema.
-                            if let Some(type_definition) =
-                                context.schema.type_by_name("Subscription")
-                            {
-                                return match type_definition {
-                                    crate::parser::schema::TypeDefinition::Object(object_type) => {
-                                        Some(&object_type.name)
-                                    }
-                                    _ => None,
-                                };
-                            }
-
-                            None
-                        })
-                }
-            },
+            Definition::Operation(operation) => context
+                .schema
+                .root_operation_type(operation)
+                .map(|root_type| &root_type.name),
         };
 
         let schema_type = schema_type_name.map(|v| Type::NamedType(v.clone()));
@@ -226,23 +217,29 @@ fn visit_directives<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for directive in directives {
+        if context.is_aborted() {
+            break;
+        }
+
         let directive_def_args = context
             .schema
             .directive_by_name(&directive.name)
             .map(|def| &def.arguments);
 
-        visitor.enter_directive(context, user_context, directive);
-        visit_arguments(
-            visitor,
-            directive_def_args,
-            &directive.arguments,
-            context,
-            user_context,
-        );
-        visitor.leave_directive(context, user_context, directive);
+        context.with_position(directive.position, |context| {
+            visitor.enter_directive(context, user_context, directive);
+            visit_arguments(
+                visitor,
+                directive_def_args,
+                &directive.arguments,
+                context,
+                user_context,
+            );
+            visitor.leave_directive(context, user_context, directive);
+        });
     }
 }
 
@@ -253,9 +250,13 @@ fn visit_arguments<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for argument in arguments {
+        if context.is_aborted() {
+            break;
+        }
+
         let arg_type = arguments_definition
             .and_then(|argument_defs| argument_defs.iter().find(|a| a.name.eq(&argument.0)))
             .map(|a| &a.value_type);
@@ -274,7 +275,7 @@ fn visit_input_value<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     match input_value {
         Value::Boolean(_) | Value::Float(_) | Value::Int(_) | Value::String(_) => {
@@ -299,6 +300,10 @@ fn visit_input_value<'a, Visitor, UserContext>(
 
             context.with_input_type(input_type, |context| {
                 for item in v {
+                    if context.is_aborted() {
+                        break;
+                    }
+
                     visit_input_value(visitor, item, context, user_context)
                 }
             });
@@ -309,10 +314,13 @@ fn visit_input_value<'a, Visitor, UserContext>(
             visitor.enter_object_value(context, user_context, v);
 
             for (sub_key, sub_value) in v.iter() {
+                if context.is_aborted() {
+                    break;
+                }
+
                 let input_type = context
                     .current_input_type_literal()
-                    .and_then(|v| context.schema.type_by_name(v.inner_type()))
-                    .and_then(|v| v.input_field_by_name(sub_key))
+                    .and_then(|v| context.schema.input_field_by_name(v.inner_type(), sub_key))
                     .map(|v| &v.value_type);
 
                 context.with_input_type(input_type, |context| {
@@ -338,19 +346,25 @@ fn visit_variable_definitions<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     for variable in variables {
-        context.with_input_type(Some(&variable.var_type), |context| {
-            visitor.enter_variable_definition(context, user_context, variable);
+        if context.is_aborted() {
+            break;
+        }
 
-            if let Some(default_value) = &variable.default_value {
-                visit_input_value(visitor, default_value, context, user_context);
-            }
+        context.with_position(variable.position, |context| {
+            context.with_input_type(Some(&variable.var_type), |context| {
+                visitor.enter_variable_definition(context, user_context, variable);
 
-            // DOTAN: We should visit the directives as well here, but it's extracted in graphql_parser.
+                if let Some(default_value) = &variable.default_value {
+                    visit_input_value(visitor, default_value, context, user_context);
+                }
+
+                // DOTAN: We should visit the directives as well here, but it's extracted in graphql_parser.
 
-            visitor.leave_variable_definition(context, user_context, variable);
+                visitor.leave_variable_definition(context, user_context, variable);
+            })
         })
     }
 }
@@ -361,24 +375,22 @@ fn visit_selection<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     match selection {
         Selection::Field(field) => {
+            let schema = context.schema;
             let parent_type_def = context
                 .current_parent_type()
-                .and_then(|t| t.field_by_name(&field.name));
+                .and_then(|t| schema.field_by_name(t.name(), &field.name));
 
             let field_type = parent_type_def.map(|f| &f.field_type);
             let field_args = parent_type_def.map(|f| &f.arguments);
 
-            context.with_type(field_type, |context| {
-                visitor.enter_field(context, user_context, field);
-                context.with_field(
-                    context
-                        .current_parent_type()
-                        .and_then(|t| t.field_by_name(&field.name)),
-                    |context| {
+            context.with_position(field.position, |context| {
+                context.with_type(field_type, |context| {
+                    visitor.enter_field(context, user_context, field);
+                    context.with_field(parent_type_def, |context| {
                         visit_arguments(
                             visitor,
                             field_args,
@@ -388,48 +400,53 @@ fn visit_selection<'a, Visitor, UserContext>(
                         );
                         visit_directives(visitor, &field.directives, context, user_context);
                         visit_selection_set(visitor, &field.selection_set, context, user_context);
-                    },
-                );
-                visitor.leave_field(context, user_context, field);
+                    });
+                    visitor.leave_field(context, user_context, field);
+                })
             });
         }
         Selection::FragmentSpread(fragment_spread) => {
-            visitor.enter_fragment_spread(context, user_context, fragment_spread);
-            visit_directives(visitor, &fragment_spread.directives, context, user_context);
-            visitor.leave_fragment_spread(context, user_context, fragment_spread);
+            context.with_position(fragment_spread.position, |context| {
+                visitor.enter_fragment_spread(context, user_context, fragment_spread);
+                visit_directives(visitor, &fragment_spread.directives, context, user_context);
+                visitor.leave_fragment_spread(context, user_context, fragment_spread);
+            })
         }
         Selection::InlineFragment(inline_fragment) => {
-            if let Some(TypeCondition::On(fragment_condition)) = &inline_fragment.type_condition {
-                context.with_type(
-                    Some(&Type::NamedType(fragment_condition.clone())),
-                    |context| {
-                        visitor.enter_inline_fragment(context, user_context, inline_fragment);
-                        visit_directives(
-                            visitor,
-                            &inline_fragment.directives,
-                            context,
-                            user_context,
-                        );
-                        visit_selection_set(
-                            visitor,
-                            &inline_fragment.selection_set,
-                            context,
-                            user_context,
-                        );
-                        visitor.leave_inline_fragment(context, user_context, inline_fragment);
-                    },
-                );
-            } else {
-                visitor.enter_inline_fragment(context, user_context, inline_fragment);
-                visit_directives(visitor, &inline_fragment.directives, context, user_context);
-                visit_selection_set(
-                    visitor,
-                    &inline_fragment.selection_set,
-                    context,
-                    user_context,
-                );
-                visitor.leave_inline_fragment(context, user_context, inline_fragment);
-            }
+            context.with_position(inline_fragment.position, |context| {
+                if let Some(TypeCondition::On(fragment_condition)) = &inline_fragment.type_condition
+                {
+                    context.with_type(
+                        Some(&Type::NamedType(fragment_condition.clone())),
+                        |context| {
+                            visitor.enter_inline_fragment(context, user_context, inline_fragment);
+                            visit_directives(
+                                visitor,
+                                &inline_fragment.directives,
+                                context,
+                                user_context,
+                            );
+                            visit_selection_set(
+                                visitor,
+                                &inline_fragment.selection_set,
+                                context,
+                                user_context,
+                            );
+                            visitor.leave_inline_fragment(context, user_context, inline_fragment);
+                        },
+                    );
+                } else {
+                    visitor.enter_inline_fragment(context, user_context, inline_fragment);
+                    visit_directives(visitor, &inline_fragment.directives, context, user_context);
+                    visit_selection_set(
+                        visitor,
+                        &inline_fragment.selection_set,
+                        context,
+                        user_context,
+                    );
+                    visitor.leave_inline_fragment(context, user_context, inline_fragment);
+                }
+            })
         }
     }
 }
@@ -440,12 +457,16 @@ fn visit_selection_set<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
     context.with_parent_type(|context| {
         visitor.enter_selection_set(context, user_context, selection_set);
 
         for selection in &selection_set.items {
+            if context.is_aborted() {
+                break;
+            }
+
             visit_selection(visitor, selection, context, user_context);
         }
 
@@ -459,12 +480,14 @@ fn visit_fragment_definition<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
-    visitor.enter_fragment_definition(context, user_context, fragment);
-    visit_directives(visitor, &fragment.directives, context, user_context);
-    visit_selection_set(visitor, &fragment.selection_set, context, user_context);
-    visitor.leave_fragment_definition(context, user_context, fragment);
+    context.with_position(fragment.position, |context| {
+        visitor.enter_fragment_definition(context, user_context, fragment);
+        visit_directives(visitor, &fragment.directives, context, user_context);
+        visit_selection_set(visitor, &fragment.selection_set, context, user_context);
+        visitor.leave_fragment_definition(context, user_context, fragment);
+    })
 }
 
 fn visit_operation_definition<'a, Visitor, UserContext>(
@@ -473,18 +496,20 @@ fn visit_operation_definition<'a, Visitor, UserContext>(
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
 ) where
-    Visitor: OperationVisitor<'a, UserContext>,
+    Visitor: OperationVisitor<'a, UserContext> + ?Sized,
 {
-    visitor.enter_operation_definition(context, user_context, operation);
-    visit_directives(visitor, operation.directives(), context, user_context);
-    visit_variable_definitions(
-        visitor,
-        operation.variable_definitions(),
-        context,
-        user_context,
-    );
-    visit_selection_set(visitor, operation.selection_set(), context, user_context);
-    visitor.leave_operation_definition(context, user_context, operation);
+    context.with_position(operation.position(), |context| {
+        visitor.enter_operation_definition(context, user_context, operation);
+        visit_directives(visitor, operation.directives(), context, user_context);
+        visit_variable_definitions(
+            visitor,
+            operation.variable_definitions(),
+            context,
+            user_context,
+        );
+        visit_selection_set(visitor, operation.selection_set(), context, user_context);
+        visitor.leave_operation_definition(context, user_context, operation);
+    })
 }
 
 // Trait
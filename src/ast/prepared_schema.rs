@@ -0,0 +1,642 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Write};
+use std::sync::OnceLock;
+
+use super::builtins::{
+    builtin_directive_by_name, builtin_directives, builtin_type_by_name, builtin_types,
+    introspection_type_by_name, meta_field_by_name,
+};
+use super::type_extensions::{apply_type_extensions, TypeExtensionError};
+use super::{
+    ext::DEFAULT_SCHEMA_DEFINITION, FieldByNameExtension, ImplementingInterfaceExtension,
+    SchemaDocumentExtension, TypeDefinitionExtension,
+};
+use crate::static_graphql::schema::{
+    self, DirectiveDefinition, InputValue, ObjectType, TypeDefinition,
+};
+
+/// A schema document prepared for validation.
+///
+/// Type, field, directive and possible-type lookups are resolved through indexes built once in
+/// `PreparedSchema::new`, instead of scanning the schema definitions on every lookup. Type
+/// extensions are merged into the types they extend, the extensions that can't be merged are
+/// left out and reported by `extension_errors`.
+/// Build it once per schema and share it between validations: it's immutable, `Send` and `Sync`.
+///
+/// The built-in scalars and directives are available even when the document doesn't define
+/// them, unless `without_builtins` is used. The introspection types and meta-fields (`__typename`,
+/// `__schema` and `__type`) are always available.
+pub struct PreparedSchema<'s> {
+    document: Cow<'s, schema::Document>,
+    fingerprint: OnceLock<u64>,
+    extension_errors: Vec<TypeExtensionError>,
+    builtins: bool,
+    schema_definition: Option<usize>,
+    types: HashMap<String, usize>,
+    directives: HashMap<String, usize>,
+    fields: HashMap<String, HashMap<String, usize>>,
+    sub_types: HashMap<String, HashSet<String>>,
+    possible_types: HashMap<String, Vec<usize>>,
+}
+
+impl<'s> PreparedSchema<'s> {
+    pub fn new(document: &'s schema::Document) -> Self {
+        Self::prepare(Cow::Borrowed(document))
+    }
+
+    fn prepare(document: Cow<'s, schema::Document>) -> Self {
+        let (document, extension_errors) = if document
+            .definitions
+            .iter()
+            .any(|definition| matches!(definition, schema::Definition::TypeExtension(_)))
+        {
+            let (merged, errors) = apply_type_extensions(&document);
+            (Cow::Owned(merged), errors)
+        } else {
+            (document, vec![])
+        };
+        let mut schema_definition = None;
+        let mut types = HashMap::new();
+        let mut directives = HashMap::new();
+        let mut fields = HashMap::new();
+        let mut sub_types: HashMap<String, HashSet<String>> = HashMap::new();
+
+        // The first definition wins, like the linear lookups on `schema::Document` do.
+        for (index, definition) in document.definitions.iter().enumerate() {
+            match definition {
+                schema::Definition::SchemaDefinition(_) => {
+                    schema_definition.get_or_insert(index);
+                }
+                schema::Definition::DirectiveDefinition(directive_def) => {
+                    directives
+                        .entry(directive_def.name.clone())
+                        .or_insert(index);
+                }
+                schema::Definition::TypeDefinition(type_def) => {
+                    if types.contains_key(type_def.name()) {
+                        continue;
+                    }
+
+                    types.insert(type_def.name().to_string(), index);
+                    fields.insert(type_def.name().to_string(), Self::index_fields(type_def));
+
+                    if let TypeDefinition::Union(union_def) = type_def {
+                        sub_types
+                            .entry(union_def.name.clone())
+                            .or_default()
+                            .extend(union_def.types.iter().cloned());
+                    }
+
+                    for interface in type_def.interfaces() {
+                        sub_types
+                            .entry(interface)
+                            .or_default()
+                            .insert(type_def.name().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut prepared = PreparedSchema {
+            fingerprint: OnceLock::new(),
+            extension_errors,
+            builtins: true,
+            document,
+            schema_definition,
+            types,
+            directives,
+            fields,
+            sub_types,
+            possible_types: HashMap::new(),
+        };
+        prepared.possible_types = prepared.index_possible_types();
+
+        prepared
+    }
+
+    fn index_fields(type_def: &TypeDefinition) -> HashMap<String, usize> {
+        let names: Vec<&String> = match type_def {
+            TypeDefinition::Object(object) => object.fields.iter().map(|f| &f.name).collect(),
+            TypeDefinition::Interface(interface) => {
+                interface.fields.iter().map(|f| &f.name).collect()
+            }
+            TypeDefinition::InputObject(input_object) => {
+                input_object.fields.iter().map(|f| &f.name).collect()
+            }
+            _ => vec![],
+        };
+
+        let mut index = HashMap::with_capacity(names.len());
+
+        for (position, name) in names.into_iter().enumerate() {
+            index.entry(name.clone()).or_insert(position);
+        }
+
+        index
+    }
+
+    fn index_possible_types(&self) -> HashMap<String, Vec<usize>> {
+        let mut possible_types: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, definition) in self.document.definitions.iter().enumerate() {
+            match definition {
+                schema::Definition::TypeDefinition(TypeDefinition::Union(union_def))
+                    if self.types.get(&union_def.name) == Some(&index) =>
+                {
+                    let members = union_def
+                        .types
+                        .iter()
+                        .filter_map(|member| self.types.get(member).copied())
+                        .filter(|member_index| self.object_at(*member_index).is_some())
+                        .collect();
+
+                    possible_types.insert(union_def.name.clone(), members);
+                }
+                schema::Definition::TypeDefinition(TypeDefinition::Object(object_def))
+                    if self.types.get(&object_def.name) == Some(&index) =>
+                {
+                    for interface in &object_def.implements_interfaces {
+                        let is_interface = matches!(
+                            self.type_by_name(interface),
+                            Some(TypeDefinition::Interface(_))
+                        );
+
+                        if is_interface {
+                            possible_types
+                                .entry(interface.clone())
+                                .or_default()
+                                .push(index);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        possible_types
+    }
+
+    fn type_at(&self, index: usize) -> Option<&TypeDefinition> {
+        match self.document.definitions.get(index) {
+            Some(schema::Definition::TypeDefinition(type_def)) => Some(type_def),
+            _ => None,
+        }
+    }
+
+    fn object_at(&self, index: usize) -> Option<&ObjectType> {
+        match self.type_at(index) {
+            Some(TypeDefinition::Object(object_def)) => Some(object_def),
+            _ => None,
+        }
+    }
+
+    /// Only resolves the types and directives defined by the document, for schemas that must
+    /// define every scalar and directive they use.
+    pub fn without_builtins(mut self) -> Self {
+        if self.builtins {
+            self.builtins = false;
+            self.fingerprint = OnceLock::new();
+        }
+
+        self
+    }
+
+    /// The schema document this was prepared from, with its type extensions merged.
+    pub fn document(&self) -> &schema::Document {
+        &self.document
+    }
+
+    /// The type extensions that couldn't be merged and are ignored by the lookups, like
+    /// `merge_type_extensions` reports them.
+    pub fn extension_errors(&self) -> &[TypeExtensionError] {
+        &self.extension_errors
+    }
+
+    /// A hash of the printed schema: equal for schemas with the same SDL, stable across runs.
+    /// Computed on first use.
+    pub fn fingerprint(&self) -> u64 {
+        *self.fingerprint.get_or_init(|| {
+            let document = fingerprint(&*self.document);
+
+            if self.builtins {
+                document
+            } else {
+                fingerprint(&format_args!("{:x} without built-ins", document))
+            }
+        })
+    }
+
+    /// Names of the directives defined by the schema, and of the built-in directives it doesn't
+    /// define, in no particular order.
+    pub fn directive_names(&self) -> impl Iterator<Item = &str> {
+        let builtins = self
+            .builtins
+            .then(builtin_directives)
+            .into_iter()
+            .flatten()
+            .map(|directive_def| directive_def.name.as_str())
+            .filter(|name| !self.directives.contains_key(*name));
+
+        self.directives.keys().map(String::as_str).chain(builtins)
+    }
+
+    /// Looks up a field of an object or interface type, or one of the meta-fields: `__typename`
+    /// on composite types, `__schema` and `__type` on the query type.
+    pub fn field_by_name(&self, type_name: &str, field_name: &str) -> Option<&schema::Field> {
+        let type_def = self.type_by_name(type_name)?;
+        let field_def = match self.fields.get(type_name) {
+            Some(fields) => fields.get(field_name).and_then(|position| match type_def {
+                TypeDefinition::Object(object) => object.fields.get(*position),
+                TypeDefinition::Interface(interface) => interface.fields.get(*position),
+                _ => None,
+            }),
+            // Introspection types aren't indexed.
+            None => type_def.field_by_name(&field_name.to_string()),
+        };
+
+        field_def.or_else(|| self.meta_field_by_name(type_def, field_name))
+    }
+
+    fn meta_field_by_name(
+        &self,
+        type_def: &TypeDefinition,
+        field_name: &str,
+    ) -> Option<&schema::Field> {
+        let is_available = match field_name {
+            "__typename" => type_def.is_composite_type(),
+            "__schema" | "__type" => {
+                self.schema_definition().query.as_deref() == Some(type_def.name())
+            }
+            _ => false,
+        };
+
+        is_available
+            .then(|| meta_field_by_name(field_name))
+            .flatten()
+    }
+
+    /// Looks up a field of an input object type.
+    pub fn input_field_by_name(&self, type_name: &str, field_name: &str) -> Option<&InputValue> {
+        let position = *self.fields.get(type_name)?.get(field_name)?;
+
+        match self.type_by_name(type_name)? {
+            TypeDefinition::InputObject(input_object) => input_object.fields.get(position),
+            _ => None,
+        }
+    }
+
+    /// The object types that may be returned for an abstract type: the members of a union, or
+    /// the object types implementing an interface, in schema order.
+    pub fn possible_types(&self, abstract_type: &TypeDefinition) -> Vec<&ObjectType> {
+        if !abstract_type.is_abstract_type() {
+            return vec![];
+        }
+
+        self.possible_types
+            .get(abstract_type.name())
+            .map(|indexes| {
+                indexes
+                    .iter()
+                    .filter_map(|index| self.object_at(*index))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl PreparedSchema<'static> {
+    /// Prepares a schema document, taking ownership of it.
+    pub fn from_document(document: schema::Document) -> Self {
+        Self::prepare(Cow::Owned(document))
+    }
+}
+
+impl From<schema::Document> for PreparedSchema<'static> {
+    fn from(document: schema::Document) -> Self {
+        Self::from_document(document)
+    }
+}
+
+impl<'s> From<&'s schema::Document> for PreparedSchema<'s> {
+    fn from(document: &'s schema::Document) -> Self {
+        Self::new(document)
+    }
+}
+
+impl<'s> SchemaDocumentExtension for PreparedSchema<'s> {
+    fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
+        match self.types.get(name) {
+            Some(index) => self.type_at(*index),
+            None if self.builtins => {
+                builtin_type_by_name(name).or_else(|| introspection_type_by_name(name))
+            }
+            None => introspection_type_by_name(name),
+        }
+    }
+
+    fn type_map(&self) -> HashMap<&str, &TypeDefinition> {
+        let mut type_map: HashMap<&str, &TypeDefinition> = self
+            .types
+            .iter()
+            .filter_map(|(name, index)| Some((name.as_str(), self.type_at(*index)?)))
+            .collect();
+
+        if self.builtins {
+            for type_def in builtin_types() {
+                type_map.entry(type_def.name()).or_insert(type_def);
+            }
+        }
+
+        type_map
+    }
+
+    fn directive_by_name(&self, name: &str) -> Option<&DirectiveDefinition> {
+        match self.directives.get(name) {
+            Some(index) => match self.document.definitions.get(*index) {
+                Some(schema::Definition::DirectiveDefinition(directive_def)) => Some(directive_def),
+                _ => None,
+            },
+            None if self.builtins => builtin_directive_by_name(name),
+            None => None,
+        }
+    }
+
+    fn schema_definition(&self) -> &schema::SchemaDefinition {
+        match self
+            .schema_definition
+            .and_then(|index| self.document.definitions.get(index))
+        {
+            Some(schema::Definition::SchemaDefinition(schema_definition)) => schema_definition,
+            _ => &DEFAULT_SCHEMA_DEFINITION,
+        }
+    }
+
+    fn is_possible_type(
+        &self,
+        abstract_type: &TypeDefinition,
+        possible_type: &TypeDefinition,
+    ) -> bool {
+        abstract_type.is_abstract_type()
+            && self
+                .sub_types
+                .get(abstract_type.name())
+                .is_some_and(|sub_types| sub_types.contains(possible_type.name()))
+    }
+}
+
+/// FNV-1a over the `Display` output of a value, without buffering it.
+pub(crate) fn fingerprint<T: Display + ?Sized>(value: &T) -> u64 {
+    struct Fnv1a(u64);
+
+    impl Write for Fnv1a {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for byte in s.bytes() {
+                self.0 ^= byte as u64;
+                self.0 = self.0.wrapping_mul(0x100000001b3);
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut hasher = Fnv1a(0xcbf29ce484222325);
+    let _ = write!(hasher, "{}", value);
+
+    hasher.0
+}
+
+#[cfg(test)]
+static TEST_PREPARED_SCHEMA: &str = "
+  schema { query: Root mutation: Mutations }
+
+  interface Node { id: ID! }
+  interface Named implements Node { id: ID! name: String }
+  type User implements Node & Named { id: ID! name: String }
+  type Group implements Node { id: ID! members: [User] }
+  union Entity = User | Group | Missing
+  input Filter { name: String limit: Int }
+  type Root { node(id: ID!): Node entity: Entity }
+  type Mutations { noop: Boolean }
+  type Root { shadowed: Int }
+
+  directive @tag(name: String!) on FIELD_DEFINITION
+";
+
+#[cfg(test)]
+fn test_schema_document() -> schema::Document {
+    crate::parser::parse_schema::<String>(TEST_PREPARED_SCHEMA)
+        .unwrap()
+        .into_static()
+}
+
+#[test]
+fn lookups_match_the_schema_document() {
+    let document = test_schema_document();
+    let prepared = PreparedSchema::new(&document);
+
+    for name in ["Node", "Named", "User", "Entity", "Filter", "Root", "Nope"] {
+        assert_eq!(
+            prepared.type_by_name(name),
+            document.type_by_name(name),
+            "type {}",
+            name
+        );
+    }
+
+    assert_eq!(prepared.type_map().len(), document.type_map().len());
+    assert_eq!(
+        prepared.directive_by_name("tag"),
+        document.directive_by_name("tag")
+    );
+    assert_eq!(
+        prepared.directive_by_name("skip"),
+        document.directive_by_name("skip")
+    );
+    assert_eq!(prepared.query_type().map(|t| t.name.as_str()), Some("Root"));
+    assert_eq!(
+        prepared.mutation_type().map(|t| t.name.as_str()),
+        Some("Mutations")
+    );
+    assert_eq!(prepared.subscription_type(), None);
+    assert_eq!(prepared.schema_definition(), document.schema_definition());
+}
+
+#[test]
+fn first_type_definition_wins() {
+    let prepared = PreparedSchema::from_document(test_schema_document());
+
+    assert!(prepared.field_by_name("Root", "node").is_some());
+    assert!(prepared.field_by_name("Root", "shadowed").is_none());
+}
+
+#[test]
+fn field_lookups() {
+    let prepared = PreparedSchema::from_document(test_schema_document());
+
+    assert_eq!(
+        prepared
+            .field_by_name("User", "name")
+            .map(|f| f.name.as_str()),
+        Some("name")
+    );
+    assert_eq!(
+        prepared
+            .field_by_name("Named", "id")
+            .map(|f| f.name.as_str()),
+        Some("id")
+    );
+    assert!(prepared.field_by_name("User", "members").is_none());
+    assert!(prepared.field_by_name("Filter", "name").is_none());
+    assert!(prepared.field_by_name("Missing", "name").is_none());
+
+    assert_eq!(
+        prepared
+            .input_field_by_name("Filter", "limit")
+            .map(|f| f.name.as_str()),
+        Some("limit")
+    );
+    assert!(prepared.input_field_by_name("User", "name").is_none());
+}
+
+#[test]
+fn possible_types_and_sub_types() {
+    let prepared = PreparedSchema::from_document(test_schema_document());
+    let type_names = |name: &str| -> Vec<String> {
+        prepared
+            .possible_types(prepared.type_by_name(name).unwrap())
+            .iter()
+            .map(|t| t.name.clone())
+            .collect()
+    };
+
+    assert_eq!(type_names("Node"), vec!["User", "Group"]);
+    assert_eq!(type_names("Named"), vec!["User"]);
+    assert_eq!(type_names("Entity"), vec!["User", "Group"]);
+    assert!(type_names("User").is_empty());
+
+    let node = prepared.type_by_name("Node").unwrap();
+    let named = prepared.type_by_name("Named").unwrap();
+    let user = prepared.type_by_name("User").unwrap();
+    assert!(node.is_abstract_type());
+    assert!(prepared.is_possible_type(node, named));
+    assert!(prepared.is_possible_type(node, user));
+    assert!(!prepared.is_possible_type(user, node));
+    assert!(prepared.is_named_subtype("Group", "Entity"));
+    assert!(!prepared.is_named_subtype("Named", "Entity"));
+}
+
+#[test]
+fn fingerprint_follows_the_printed_schema() {
+    let document = test_schema_document();
+    let reparsed = crate::parser::parse_schema::<String>(&document.to_string())
+        .unwrap()
+        .into_static();
+    let changed = crate::parser::parse_schema::<String>("type Query { a: Int }")
+        .unwrap()
+        .into_static();
+
+    assert_eq!(
+        PreparedSchema::new(&document).fingerprint(),
+        PreparedSchema::from_document(reparsed).fingerprint()
+    );
+    assert_ne!(
+        PreparedSchema::new(&document).fingerprint(),
+        PreparedSchema::new(&changed).fingerprint()
+    );
+}
+
+#[test]
+fn builtins_unless_opted_out() {
+    let document = crate::parser::parse_schema::<String>(
+        "type Query { a: Int } scalar String @specifiedBy(url: \"https://example.com\")",
+    )
+    .unwrap()
+    .into_static();
+    let prepared = PreparedSchema::new(&document);
+
+    assert!(prepared.type_by_name("Int").is_some());
+    assert_eq!(
+        prepared.type_by_name("String"),
+        document.type_by_name("String")
+    );
+    assert_eq!(prepared.type_map().len(), 6);
+    assert!(prepared.directive_by_name("oneOf").is_some());
+    assert_eq!(prepared.directive_names().count(), 5);
+
+    let strict = PreparedSchema::new(&document).without_builtins();
+
+    assert!(strict.type_by_name("Int").is_none());
+    assert!(strict.type_by_name("String").is_some());
+    assert_eq!(strict.type_map().len(), 2);
+    assert!(strict.directive_by_name("skip").is_none());
+    assert_eq!(strict.directive_names().count(), 0);
+    assert_ne!(strict.fingerprint(), prepared.fingerprint());
+}
+
+#[test]
+fn introspection_lookups() {
+    let prepared = PreparedSchema::from_document(test_schema_document());
+
+    assert!(prepared.type_by_name("__Schema").is_some());
+    assert!(!prepared.type_map().contains_key("__Schema"));
+    assert_eq!(
+        prepared
+            .field_by_name("__Type", "fields")
+            .map(|f| f.arguments.len()),
+        Some(1)
+    );
+    assert!(prepared.field_by_name("__Type", "__typename").is_some());
+    assert!(prepared.field_by_name("User", "__typename").is_some());
+    assert!(prepared.field_by_name("Entity", "__typename").is_some());
+    assert!(prepared.field_by_name("Filter", "__typename").is_none());
+    assert!(prepared.field_by_name("Root", "__schema").is_some());
+    assert!(prepared.field_by_name("User", "__type").is_none());
+    assert!(prepared.field_by_name("User", "__nothing").is_none());
+    assert!(PreparedSchema::from_document(test_schema_document())
+        .without_builtins()
+        .type_by_name("__Type")
+        .is_some());
+}
+
+#[test]
+fn type_extensions_are_merged() {
+    let document = crate::parser::parse_schema::<String>(
+        "schema { query: Query mutation: Mutation }
+        type Query { a: Int }
+        extend type Query { b: Int }
+        extend type Mutation { c: Int }
+        type Mutation { d: Int }
+        extend type Missing { e: Int }",
+    )
+    .unwrap()
+    .into_static();
+    let prepared = PreparedSchema::new(&document);
+
+    assert!(prepared.field_by_name("Query", "b").is_some());
+    assert_eq!(
+        prepared.mutation_type().map(|t| t
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>()),
+        Some(vec!["d", "c"])
+    );
+    assert!(prepared.type_by_name("Missing").is_none());
+    assert_eq!(
+        prepared
+            .extension_errors()
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>(),
+        vec!["Cannot extend type \"Missing\" because it is not defined."]
+    );
+    assert!(PreparedSchema::new(
+        &crate::parser::parse_schema::<String>("type Query { a: Int }")
+            .unwrap()
+            .into_static()
+    )
+    .extension_errors()
+    .is_empty());
+}
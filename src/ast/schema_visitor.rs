@@ -1,6 +1,8 @@
 use crate::static_graphql::schema::{
-    Definition, DirectiveDefinition, Document, EnumType, EnumValue, Field, InputObjectType,
-    InputValue, InterfaceType, ObjectType, ScalarType, SchemaDefinition, TypeDefinition, UnionType,
+    Definition, DirectiveDefinition, Document, EnumType, EnumTypeExtension, EnumValue, Field,
+    InputObjectType, InputObjectTypeExtension, InputValue, InterfaceType, InterfaceTypeExtension,
+    ObjectType, ObjectTypeExtension, ScalarType, ScalarTypeExtension, SchemaDefinition,
+    TypeDefinition, TypeExtension, UnionType, UnionTypeExtension,
 };
 
 /// A trait for implenenting a visitor for GraphQL schema definition.
@@ -83,9 +85,37 @@ pub trait SchemaVisitor<T = ()> {
                     self.enter_directive_definition(directive_definition, _visitor_context);
                     self.leave_directive_definition(directive_definition, _visitor_context);
                 }
-                Definition::TypeExtension(_type_extension) => {
-                    // TODO: implement this
-                    panic!("TypeExtension not supported at the moment");
+                Definition::TypeExtension(type_extension) => {
+                    self.enter_type_extension(type_extension, _visitor_context);
+
+                    match type_extension {
+                        TypeExtension::Object(object) => {
+                            self.enter_object_type_extension(object, _visitor_context);
+                            self.leave_object_type_extension(object, _visitor_context);
+                        }
+                        TypeExtension::Scalar(scalar) => {
+                            self.enter_scalar_type_extension(scalar, _visitor_context);
+                            self.leave_scalar_type_extension(scalar, _visitor_context);
+                        }
+                        TypeExtension::Enum(enum_) => {
+                            self.enter_enum_type_extension(enum_, _visitor_context);
+                            self.leave_enum_type_extension(enum_, _visitor_context);
+                        }
+                        TypeExtension::Union(union) => {
+                            self.enter_union_type_extension(union, _visitor_context);
+                            self.leave_union_type_extension(union, _visitor_context);
+                        }
+                        TypeExtension::InputObject(input_object) => {
+                            self.enter_input_object_type_extension(input_object, _visitor_context);
+                            self.leave_input_object_type_extension(input_object, _visitor_context);
+                        }
+                        TypeExtension::Interface(interface) => {
+                            self.enter_interface_type_extension(interface, _visitor_context);
+                            self.leave_interface_type_extension(interface, _visitor_context);
+                        }
+                    }
+
+                    self.leave_type_extension(type_extension, _visitor_context);
                 }
             }
         }
@@ -170,6 +200,49 @@ pub trait SchemaVisitor<T = ()> {
 
     fn enter_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
     fn leave_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
+
+    /// Type extensions are visited as written, see `merge_type_extensions` to apply them to the
+    /// types they extend.
+    fn enter_type_extension(&self, _node: &TypeExtension, _visitor_context: &mut T) {}
+    fn leave_type_extension(&self, _node: &TypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_object_type_extension(&self, _node: &ObjectTypeExtension, _visitor_context: &mut T) {}
+    fn leave_object_type_extension(&self, _node: &ObjectTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_interface_type_extension(
+        &self,
+        _node: &InterfaceTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+    fn leave_interface_type_extension(
+        &self,
+        _node: &InterfaceTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+
+    fn enter_input_object_type_extension(
+        &self,
+        _node: &InputObjectTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+    fn leave_input_object_type_extension(
+        &self,
+        _node: &InputObjectTypeExtension,
+        _visitor_context: &mut T,
+    ) {
+    }
+
+    fn enter_union_type_extension(&self, _node: &UnionTypeExtension, _visitor_context: &mut T) {}
+    fn leave_union_type_extension(&self, _node: &UnionTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_scalar_type_extension(&self, _node: &ScalarTypeExtension, _visitor_context: &mut T) {}
+    fn leave_scalar_type_extension(&self, _node: &ScalarTypeExtension, _visitor_context: &mut T) {}
+
+    fn enter_enum_type_extension(&self, _node: &EnumTypeExtension, _visitor_context: &mut T) {}
+    fn leave_enum_type_extension(&self, _node: &EnumTypeExtension, _visitor_context: &mut T) {}
 }
 
 #[test]
@@ -377,3 +450,72 @@ fn visit_schema() {
         vec!["UsersFilter.name"]
     );
 }
+
+#[test]
+fn visit_schema_with_type_extensions() {
+    use crate::parser::schema::parse_schema;
+    let schema_ast = parse_schema(
+        r#"
+    type Query {
+      user: User
+    }
+
+    extend type Query {
+      users: [User!]!
+    }
+
+    type User {
+      id: ID!
+    }
+
+    extend type User @key(fields: "id")
+    extend scalar ID @specifiedBy(url: "https://example.com")
+    "#,
+    )
+    .expect("Failed to parse schema");
+
+    struct TestVisitor;
+
+    impl SchemaVisitor<Vec<String>> for TestVisitor {
+        fn enter_object_type(&self, _node: &ObjectType, _visitor_context: &mut Vec<String>) {
+            _visitor_context.push(format!("type {}", _node.name));
+        }
+
+        fn enter_type_extension(&self, _node: &TypeExtension, _visitor_context: &mut Vec<String>) {
+            _visitor_context.push("extension".to_string());
+        }
+
+        fn enter_object_type_extension(
+            &self,
+            _node: &ObjectTypeExtension,
+            _visitor_context: &mut Vec<String>,
+        ) {
+            _visitor_context.push(format!("extend type {}", _node.name));
+        }
+
+        fn enter_scalar_type_extension(
+            &self,
+            _node: &ScalarTypeExtension,
+            _visitor_context: &mut Vec<String>,
+        ) {
+            _visitor_context.push(format!("extend scalar {}", _node.name));
+        }
+    }
+
+    let mut collected = vec![];
+    TestVisitor {}.visit_schema_document(&schema_ast, &mut collected);
+
+    assert_eq!(
+        collected,
+        vec![
+            "type Query",
+            "extension",
+            "extend type Query",
+            "type User",
+            "extension",
+            "extend type User",
+            "extension",
+            "extend scalar ID",
+        ]
+    );
+}
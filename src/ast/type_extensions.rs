@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use super::builtins::builtin_type_by_name;
+use super::TypeDefinitionExtension;
+use crate::parser::Pos;
+use crate::static_graphql::schema::{self, TypeDefinition, TypeExtension};
+
+/// A type extension that can't be merged into the type it extends.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExtensionError {
+    /// The extended type is not defined by the schema.
+    UnknownType { type_name: String, position: Pos },
+    /// The extension is not of the same kind as the extended type (e.g. `extend enum` of an
+    /// object type).
+    KindMismatch {
+        type_name: String,
+        kind: &'static str,
+        position: Pos,
+    },
+    /// The extension redefines a field of the extended type.
+    DuplicateField {
+        type_name: String,
+        field_name: String,
+        position: Pos,
+    },
+    /// The extension redefines a value of the extended enum.
+    DuplicateEnumValue {
+        type_name: String,
+        value_name: String,
+        position: Pos,
+    },
+}
+
+impl TypeExtensionError {
+    pub fn position(&self) -> Pos {
+        match self {
+            TypeExtensionError::UnknownType { position, .. }
+            | TypeExtensionError::KindMismatch { position, .. }
+            | TypeExtensionError::DuplicateField { position, .. }
+            | TypeExtensionError::DuplicateEnumValue { position, .. } => *position,
+        }
+    }
+}
+
+impl Display for TypeExtensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeExtensionError::UnknownType { type_name, .. } => write!(
+                f,
+                "Cannot extend type \"{}\" because it is not defined.",
+                type_name
+            ),
+            TypeExtensionError::KindMismatch {
+                type_name, kind, ..
+            } => write!(f, "Cannot extend non-{} type \"{}\".", kind, type_name),
+            TypeExtensionError::DuplicateField {
+                type_name,
+                field_name,
+                ..
+            } => write!(
+                f,
+                "Field \"{}.{}\" already exists in the schema. It cannot also be defined in this type extension.",
+                type_name, field_name
+            ),
+            TypeExtensionError::DuplicateEnumValue {
+                type_name,
+                value_name,
+                ..
+            } => write!(
+                f,
+                "Enum value \"{}.{}\" already exists in the schema. It cannot also be defined in this type extension.",
+                type_name, value_name
+            ),
+        }
+    }
+}
+
+impl Error for TypeExtensionError {}
+
+/// Merges the type extensions of a schema (`extend type`, `extend interface`, `extend union`,
+/// `extend enum`, `extend input` and `extend scalar`) into the types they extend, so the schema
+/// can be used without them. Extensions of the built-in scalars add the scalar to the schema.
+///
+/// `graphql_parser` doesn't parse `extend schema`, schemas must define their root types in a
+/// single `schema` definition.
+pub fn merge_type_extensions(
+    document: &schema::Document,
+) -> Result<schema::Document, Vec<TypeExtensionError>> {
+    let (merged, errors) = apply_type_extensions(document);
+
+    if errors.is_empty() {
+        Ok(merged)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Merges the type extensions that can be merged, and returns the errors of the others.
+pub(crate) fn apply_type_extensions(
+    document: &schema::Document,
+) -> (schema::Document, Vec<TypeExtensionError>) {
+    let mut definitions = Vec::with_capacity(document.definitions.len());
+    let mut indexes: HashMap<&str, usize> = HashMap::new();
+    let mut extensions = vec![];
+
+    for definition in &document.definitions {
+        match definition {
+            schema::Definition::TypeExtension(extension) => extensions.push(extension),
+            schema::Definition::TypeDefinition(type_def) => {
+                // The first definition wins, like the schema lookups do.
+                indexes.entry(type_def.name()).or_insert(definitions.len());
+                definitions.push(definition.clone());
+            }
+            _ => definitions.push(definition.clone()),
+        }
+    }
+
+    let mut errors = vec![];
+
+    for extension in extensions {
+        let (type_name, position) = name_and_position(extension);
+        let index = match indexes.get(type_name) {
+            Some(index) => *index,
+            None => match builtin_type_by_name(type_name) {
+                Some(builtin) => {
+                    indexes.insert(type_name, definitions.len());
+                    definitions.push(schema::Definition::TypeDefinition(builtin.clone()));
+                    definitions.len() - 1
+                }
+                None => {
+                    errors.push(TypeExtensionError::UnknownType {
+                        type_name: type_name.to_string(),
+                        position,
+                    });
+                    continue;
+                }
+            },
+        };
+
+        if let schema::Definition::TypeDefinition(type_def) = &mut definitions[index] {
+            extend_type(type_def, extension, &mut errors);
+        }
+    }
+
+    (schema::Document { definitions }, errors)
+}
+
+fn name_and_position(extension: &TypeExtension) -> (&str, Pos) {
+    match extension {
+        TypeExtension::Scalar(scalar) => (&scalar.name, scalar.position),
+        TypeExtension::Object(object) => (&object.name, object.position),
+        TypeExtension::Interface(interface) => (&interface.name, interface.position),
+        TypeExtension::Union(union) => (&union.name, union.position),
+        TypeExtension::Enum(enum_) => (&enum_.name, enum_.position),
+        TypeExtension::InputObject(input_object) => (&input_object.name, input_object.position),
+    }
+}
+
+fn kind(extension: &TypeExtension) -> &'static str {
+    match extension {
+        TypeExtension::Scalar(_) => "scalar",
+        TypeExtension::Object(_) => "object",
+        TypeExtension::Interface(_) => "interface",
+        TypeExtension::Union(_) => "union",
+        TypeExtension::Enum(_) => "enum",
+        TypeExtension::InputObject(_) => "input object",
+    }
+}
+
+fn extend_type(
+    type_def: &mut TypeDefinition,
+    extension: &TypeExtension,
+    errors: &mut Vec<TypeExtensionError>,
+) {
+    match (type_def, extension) {
+        (TypeDefinition::Scalar(scalar), TypeExtension::Scalar(scalar_extension)) => {
+            scalar
+                .directives
+                .extend(scalar_extension.directives.iter().cloned());
+        }
+        (TypeDefinition::Object(object), TypeExtension::Object(object_extension)) => {
+            extend_names(
+                &mut object.implements_interfaces,
+                &object_extension.implements_interfaces,
+            );
+            object
+                .directives
+                .extend(object_extension.directives.iter().cloned());
+            extend_fields(
+                &object.name,
+                &mut object.fields,
+                &object_extension.fields,
+                |field| (&field.name, field.position),
+                errors,
+            );
+        }
+        (TypeDefinition::Interface(interface), TypeExtension::Interface(interface_extension)) => {
+            extend_names(
+                &mut interface.implements_interfaces,
+                &interface_extension.implements_interfaces,
+            );
+            interface
+                .directives
+                .extend(interface_extension.directives.iter().cloned());
+            extend_fields(
+                &interface.name,
+                &mut interface.fields,
+                &interface_extension.fields,
+                |field| (&field.name, field.position),
+                errors,
+            );
+        }
+        (TypeDefinition::Union(union), TypeExtension::Union(union_extension)) => {
+            union
+                .directives
+                .extend(union_extension.directives.iter().cloned());
+            extend_names(&mut union.types, &union_extension.types);
+        }
+        (TypeDefinition::Enum(enum_), TypeExtension::Enum(enum_extension)) => {
+            enum_
+                .directives
+                .extend(enum_extension.directives.iter().cloned());
+
+            for value in &enum_extension.values {
+                if enum_.values.iter().any(|v| v.name == value.name) {
+                    errors.push(TypeExtensionError::DuplicateEnumValue {
+                        type_name: enum_.name.clone(),
+                        value_name: value.name.clone(),
+                        position: value.position,
+                    });
+                } else {
+                    enum_.values.push(value.clone());
+                }
+            }
+        }
+        (
+            TypeDefinition::InputObject(input_object),
+            TypeExtension::InputObject(input_object_extension),
+        ) => {
+            input_object
+                .directives
+                .extend(input_object_extension.directives.iter().cloned());
+            extend_fields(
+                &input_object.name,
+                &mut input_object.fields,
+                &input_object_extension.fields,
+                |field| (&field.name, field.position),
+                errors,
+            );
+        }
+        (type_def, extension) => errors.push(TypeExtensionError::KindMismatch {
+            type_name: type_def.name().to_string(),
+            kind: kind(extension),
+            position: name_and_position(extension).1,
+        }),
+    }
+}
+
+/// Adds the interfaces or union members that aren't there yet.
+fn extend_names(names: &mut Vec<String>, additions: &[String]) {
+    for name in additions {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+}
+
+fn extend_fields<T: Clone>(
+    type_name: &str,
+    fields: &mut Vec<T>,
+    additions: &[T],
+    name_and_position: impl Fn(&T) -> (&String, Pos),
+    errors: &mut Vec<TypeExtensionError>,
+) {
+    for field in additions {
+        let (field_name, position) = name_and_position(field);
+
+        if fields.iter().any(|f| name_and_position(f).0 == field_name) {
+            errors.push(TypeExtensionError::DuplicateField {
+                type_name: type_name.to_string(),
+                field_name: field_name.clone(),
+                position,
+            });
+        } else {
+            fields.push(field.clone());
+        }
+    }
+}
+
+#[test]
+fn merges_type_extensions() {
+    let document = crate::parser::parse_schema::<String>(
+        "extend type Query { users: [User] }
+        type Query { user: User }
+        type User { id: ID! }
+        extend type User implements Node @key(fields: \"id\") { name: String }
+        interface Node { id: ID! }
+        union Entity = User
+        extend union Entity = Query | User
+        enum Role { USER }
+        extend enum Role { ADMIN }
+        input Filter { name: String }
+        extend input Filter { limit: Int }
+        extend scalar String @specifiedBy(url: \"https://example.com\")",
+    )
+    .unwrap()
+    .into_static();
+    let merged = merge_type_extensions(&document).unwrap();
+    let expected = crate::parser::parse_schema::<String>(
+        "type Query { user: User users: [User] }
+        type User implements Node @key(fields: \"id\") { id: ID! name: String }
+        interface Node { id: ID! }
+        union Entity = User | Query
+        enum Role { USER ADMIN }
+        input Filter { name: String limit: Int }
+        scalar String @specifiedBy(url: \"https://example.com\")",
+    )
+    .unwrap()
+    .into_static();
+
+    assert_eq!(merged.to_string(), expected.to_string());
+}
+
+#[test]
+fn type_extension_conflicts() {
+    let document = crate::parser::parse_schema::<String>(
+        "type Query { user: String }
+        enum Role { USER }
+        extend type Query { user: String other: Int }
+        extend enum Role { USER }
+        extend input Query { name: String }
+        extend type Missing { id: ID }",
+    )
+    .unwrap()
+    .into_static();
+    let errors = merge_type_extensions(&document).unwrap_err();
+
+    assert_eq!(
+        errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        vec![
+            "Field \"Query.user\" already exists in the schema. It cannot also be defined in this type extension.",
+            "Enum value \"Role.USER\" already exists in the schema. It cannot also be defined in this type extension.",
+            "Cannot extend non-input object type \"Query\".",
+            "Cannot extend type \"Missing\" because it is not defined.",
+        ]
+    );
+    assert_eq!(
+        errors[3].position(),
+        Pos {
+            line: 6,
+            column: 16
+        }
+    );
+
+    let (merged, _) = apply_type_extensions(&document);
+
+    assert_eq!(
+        merged
+            .definitions
+            .iter()
+            .filter(|d| matches!(d, schema::Definition::TypeExtension(_)))
+            .count(),
+        0
+    );
+}
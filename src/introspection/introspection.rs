@@ -94,6 +94,9 @@ pub struct IntrospectionInputObjectType {
     pub description: Option<String>,
     #[serde(rename = "inputFields")]
     pub input_fields: Vec<IntrospectionInputValue>,
+    /// Only present in introspection results of servers supporting `@oneOf`.
+    #[serde(rename = "isOneOf")]
+    pub is_one_of: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -259,3 +262,50 @@ fn test_shopify_introspection() {
         .expect("failed to open json file");
     parse_introspection(json_file).expect("failed to parse introspection json");
 }
+
+#[test]
+fn test_one_of_introspection() {
+    let introspection = parse_introspection_from_string(
+        r#"{
+          "__schema": {
+            "description": null,
+            "queryType": { "name": "Query" },
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [
+              {
+                "kind": "INPUT_OBJECT",
+                "name": "UserBy",
+                "description": null,
+                "isOneOf": true,
+                "inputFields": [
+                  {
+                    "name": "id",
+                    "description": null,
+                    "defaultValue": null,
+                    "type": { "kind": "SCALAR", "name": "ID" }
+                  }
+                ]
+              }
+            ],
+            "directives": [
+              {
+                "name": "oneOf",
+                "description": null,
+                "isRepeatable": false,
+                "locations": ["INPUT_OBJECT"],
+                "args": []
+              }
+            ]
+          }
+        }"#,
+    )
+    .expect("failed to parse introspection json");
+
+    match &introspection.__schema.types[0] {
+        IntrospectionType::INPUT_OBJECT(input_object) => {
+            assert_eq!(input_object.is_one_of, Some(true))
+        }
+        _ => panic!("expected an input object type"),
+    }
+}
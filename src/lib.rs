@@ -28,7 +28,9 @@ pub mod static_graphql {
     static_graphql!(schema, schema, {
       Field, Directive, InterfaceType, ObjectType, Value, TypeDefinition,
       EnumType, Type, Document, ScalarType, InputValue, DirectiveDefinition,
-      UnionType, InputObjectType, EnumValue, SchemaDefinition,
+      UnionType, InputObjectType, EnumValue, SchemaDefinition, TypeExtension,
+      ScalarTypeExtension, ObjectTypeExtension, InterfaceTypeExtension, UnionTypeExtension,
+      EnumTypeExtension, InputObjectTypeExtension,
     });
 }
 
@@ -40,3 +42,28 @@ pub mod validation;
 pub extern crate graphql_parser as parser;
 #[cfg(feature = "graphql_parser_fork")]
 pub extern crate graphql_parser_hive_fork as parser;
+
+#[test]
+fn duplicate_input_fields_are_merged_by_the_parser() {
+    // Both parsers keep a single value per object field, which blocks `UniqueInputFieldNames`
+    // (see "Validation Rules" in the README): this fails once they keep duplicate fields.
+    use crate::parser::query::{Definition, OperationDefinition, Selection, Value};
+
+    let document = crate::parser::parse_query::<String>("{ f(input: { a: 1, a: 2 }) }").unwrap();
+    let Definition::Operation(OperationDefinition::SelectionSet(selection_set)) =
+        &document.definitions[0]
+    else {
+        panic!("expected a query shorthand");
+    };
+    let Selection::Field(field) = &selection_set.items[0] else {
+        panic!("expected a field");
+    };
+
+    match &field.arguments[0].1 {
+        Value::Object(fields) => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields.get("a"), Some(&Value::Int(2.into())));
+        }
+        value => panic!("expected an object, found {}", value),
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use super::utils::ValidationError;
+use super::validate::{validate_with_prepared_schema, ValidationPlan};
+use crate::ast::{prepared_schema::fingerprint, PreparedSchema};
+use crate::static_graphql::query;
+
+/// Narrows the lookup to the entries of a plan whose operations print the same way. Entries are
+/// then compared to the operation itself, so hash collisions and operations that only differ in
+/// formatting (and so in the locations of their errors) never share results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    document: u64,
+    plan: u64,
+}
+
+struct CacheEntry {
+    document: query::Document,
+    errors: Vec<ValidationError>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    schema: Option<u64>,
+    tick: u64,
+    len: usize,
+    entries: HashMap<CacheKey, Vec<CacheEntry>>,
+    recency: BTreeMap<u64, CacheKey>,
+}
+
+impl CacheState {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.len = 0;
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn evict(&mut self, key: CacheKey, last_used: u64) {
+        if let Some(bucket) = self.entries.get_mut(&key) {
+            bucket.retain(|entry| entry.last_used != last_used);
+
+            if bucket.is_empty() {
+                self.entries.remove(&key);
+            }
+
+            self.len -= 1;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidationCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+/// A bounded, thread-safe LRU cache of validation results.
+///
+/// Results are keyed by the operation document and a fingerprint of the `ValidationPlan` (see
+/// `ValidationRule::fingerprint`). Operations are compared with their positions, so the same
+/// operation formatted differently is cached separately, with its own error locations.
+/// The cache holds results for a single schema at a time:
+/// validating against a schema with another fingerprint drops all the cached results.
+///
+/// Validation runs outside of the cache lock, so concurrent misses for the same operation may
+/// validate it more than once.
+pub struct ValidationCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ValidationCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Same as `validate_with_prepared_schema`, returning the cached errors when the same
+    /// operation was already validated against this schema with an identical plan.
+    pub fn validate<'a>(
+        &self,
+        schema: &'a PreparedSchema<'a>,
+        operation: &'a query::Document,
+        validation_plan: &'a ValidationPlan,
+    ) -> Vec<ValidationError> {
+        let key = CacheKey {
+            document: fingerprint(operation),
+            plan: validation_plan.fingerprint(),
+        };
+
+        if let Some(errors) = self.get(schema.fingerprint(), key, operation) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return errors;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let errors = validate_with_prepared_schema(schema, operation, validation_plan);
+        self.insert(schema.fingerprint(), key, operation, errors.clone());
+
+        errors
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        // The state is consistent after every operation, a panic elsewhere can't corrupt it.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(
+        &self,
+        schema: u64,
+        key: CacheKey,
+        document: &query::Document,
+    ) -> Option<Vec<ValidationError>> {
+        let mut state = self.lock();
+
+        if state.schema != Some(schema) {
+            return None;
+        }
+
+        let tick = state.next_tick();
+        let entry = state
+            .entries
+            .get_mut(&key)?
+            .iter_mut()
+            .find(|entry| entry.document == *document)?;
+        let previous = std::mem::replace(&mut entry.last_used, tick);
+        let errors = entry.errors.clone();
+        state.recency.remove(&previous);
+        state.recency.insert(tick, key);
+
+        Some(errors)
+    }
+
+    fn insert(
+        &self,
+        schema: u64,
+        key: CacheKey,
+        document: &query::Document,
+        errors: Vec<ValidationError>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+
+        if state.schema != Some(schema) {
+            state.clear();
+            state.schema = Some(schema);
+        }
+
+        let tick = state.next_tick();
+        let bucket = state.entries.entry(key).or_default();
+
+        match bucket.iter_mut().find(|entry| entry.document == *document) {
+            Some(entry) => {
+                let previous = std::mem::replace(&mut entry.last_used, tick);
+                entry.errors = errors;
+                state.recency.remove(&previous);
+            }
+            None => {
+                bucket.push(CacheEntry {
+                    document: document.clone(),
+                    errors,
+                    last_used: tick,
+                });
+                state.len += 1;
+            }
+        }
+
+        state.recency.insert(tick, key);
+
+        while state.len > self.capacity {
+            match state.recency.pop_first() {
+                Some((last_used, oldest)) => state.evict(oldest, last_used),
+                None => break,
+            };
+        }
+    }
+
+    /// Drops all the cached results. Hit and miss counters are kept.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.clear();
+        state.schema = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> ValidationCacheStats {
+        ValidationCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse_operation(operation: &str) -> query::Document {
+    crate::parser::parse_query(operation).unwrap().into_static()
+}
+
+#[test]
+fn returns_cached_errors() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let cache = ValidationCache::new(10);
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let schema = PreparedSchema::new(&schema_ast);
+    let operation = parse_operation("{ dog { unknownField } }");
+
+    let first = cache.validate(&schema, &operation, &plan);
+    let second = cache.validate(&schema, &operation, &plan);
+
+    assert_eq!(
+        get_messages(&first),
+        vec!["Cannot query field \"unknownField\" on type \"Dog\"."]
+    );
+    assert_eq!(get_messages(&first), get_messages(&second));
+    assert_eq!(
+        cache.stats(),
+        ValidationCacheStats {
+            hits: 1,
+            misses: 1,
+            entries: 1
+        }
+    );
+
+    // The same operation text parsed again is a hit as well.
+    cache.validate(&schema, &parse_operation("{ dog { unknownField } }"), &plan);
+    assert_eq!(cache.stats().hits, 2);
+}
+
+#[test]
+fn formatting_is_part_of_the_key() {
+    use crate::parser::Pos;
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let cache = ValidationCache::new(10);
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let schema = PreparedSchema::new(&schema_ast);
+    let compact = parse_operation("{ dog { unknownField } }");
+    let indented = parse_operation(
+        "
+        {
+          dog {
+            unknownField
+          }
+        }",
+    );
+
+    // Both operations print the same way.
+    assert_eq!(compact.to_string(), indented.to_string());
+
+    let compact_errors = cache.validate(&schema, &compact, &plan);
+    let indented_errors = cache.validate(&schema, &indented, &plan);
+
+    assert_eq!(
+        get_messages(&compact_errors),
+        get_messages(&indented_errors)
+    );
+    assert_eq!(
+        compact_errors[0].locations,
+        vec![Pos { line: 1, column: 9 }]
+    );
+    assert_eq!(
+        indented_errors[0].locations,
+        vec![Pos {
+            line: 4,
+            column: 13
+        }]
+    );
+    assert_eq!(
+        cache.stats(),
+        ValidationCacheStats {
+            hits: 0,
+            misses: 2,
+            entries: 2
+        }
+    );
+
+    assert_eq!(
+        cache.validate(&schema, &indented, &plan)[0].locations,
+        indented_errors[0].locations
+    );
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn plans_are_cached_separately() {
+    use crate::validation::rules::{default_rules_validation_plan, FieldsOnCorrectType};
+    use crate::validation::test_utils::*;
+
+    let cache = ValidationCache::new(10);
+    let default_plan = default_rules_validation_plan();
+    let empty_plan = ValidationPlan::new();
+    let single_rule_plan = create_plan_from_rule(Box::new(FieldsOnCorrectType::new()));
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let schema = PreparedSchema::new(&schema_ast);
+    let operation = parse_operation("{ dog { unknownField } }");
+
+    assert_eq!(cache.validate(&schema, &operation, &default_plan).len(), 1);
+    assert_eq!(cache.validate(&schema, &operation, &empty_plan).len(), 0);
+    assert_eq!(
+        cache.validate(&schema, &operation, &single_rule_plan).len(),
+        1
+    );
+    assert_eq!(cache.stats().misses, 3);
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let cache = ValidationCache::new(2);
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let schema = PreparedSchema::new(&schema_ast);
+    let a = parse_operation("{ dog { name } }");
+    let b = parse_operation("{ dog { nickname } }");
+    let c = parse_operation("{ dog { barkVolume } }");
+
+    cache.validate(&schema, &a, &plan);
+    cache.validate(&schema, &b, &plan);
+    // `a` is now more recent than `b`
+    cache.validate(&schema, &a, &plan);
+    cache.validate(&schema, &c, &plan);
+    assert_eq!(cache.len(), 2);
+
+    cache.validate(&schema, &a, &plan);
+    cache.validate(&schema, &c, &plan);
+    assert_eq!(cache.stats().hits, 3);
+
+    cache.validate(&schema, &b, &plan);
+    assert_eq!(cache.stats().misses, 4);
+}
+
+#[test]
+fn swapping_the_schema_invalidates() {
+    use crate::validation::rules::default_rules_validation_plan;
+
+    let cache = ValidationCache::new(10);
+    let plan = default_rules_validation_plan();
+    let operation = parse_operation("{ field }");
+    let before = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>("type Query { other: String }")
+            .unwrap()
+            .into_static(),
+    );
+    let after = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>("type Query { field: String }")
+            .unwrap()
+            .into_static(),
+    );
+
+    assert_eq!(cache.validate(&before, &operation, &plan).len(), 1);
+    assert_eq!(cache.validate(&after, &operation, &plan).len(), 0);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.validate(&before, &operation, &plan).len(), 1);
+    assert_eq!(cache.stats().misses, 3);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.stats().misses, 3);
+}
+
+#[test]
+fn is_shared_between_threads() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let cache = ValidationCache::new(10);
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let schema = PreparedSchema::new(&schema_ast);
+    let operation = parse_operation("{ dog { unknownField } }");
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    assert_eq!(cache.validate(&schema, &operation, &plan).len(), 1);
+                }
+            });
+        }
+    });
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits + stats.misses, 40);
+    assert_eq!(stats.entries, 1);
+}
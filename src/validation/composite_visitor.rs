@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use super::{
+    rules::ValidationRule,
+    utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails},
+    validate::ValidationOptions,
+};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+
+type RuleVisitor<'a> = Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>;
+
+pub(crate) const TOO_MANY_ERRORS: &str =
+    "Too many validation errors, error limit reached. Validation aborted.";
+
+/// The error ending the errors of an aborted validation.
+pub(crate) fn aborted(message: String) -> ValidationError {
+    ValidationError {
+        error_code: "ValidationAborted".into(),
+        locations: vec![],
+        message,
+        details: ValidationErrorDetails::default(),
+    }
+}
+
+macro_rules! fan_out {
+    ($self:ident, $method:ident, $context:ident, $node:expr) => {
+        if $self.abort_error.is_none() {
+            for (visitor, errors) in $self.visitors.iter_mut() {
+                let reported = errors.errors.len();
+                visitor.$method($context, errors, $node);
+                $self.error_count += errors.errors.len() - reported;
+            }
+
+            $self.check_limits($context);
+        }
+    };
+}
+
+/// Same as `fan_out`, counting the node towards `ValidationOptions::max_nodes`.
+macro_rules! fan_out_enter {
+    ($self:ident, $method:ident, $context:ident, $node:expr) => {
+        $self.visited_nodes += 1;
+        fan_out!($self, $method, $context, $node);
+    };
+}
+
+/// Drives the visitors of many validation rules in a single traversal.
+///
+/// Every enter/leave callback is forwarded to all rule visitors, in rule order. Each rule
+/// reports into its own error collector, and `into_errors` concatenates them in rule order,
+/// so the output is identical to running the rules one after the other.
+///
+/// When a limit of its `ValidationOptions` is reached, the traversal is aborted and
+/// `into_errors` ends with an error explaining why.
+pub struct CompositeVisitor<'a> {
+    visitors: Vec<(RuleVisitor<'a>, ValidationErrorContext)>,
+    options: ValidationOptions,
+    visited_nodes: usize,
+    /// The errors reported by all the visitors so far.
+    error_count: usize,
+    started_at: Instant,
+    abort_error: Option<ValidationError>,
+}
+
+impl<'a> CompositeVisitor<'a> {
+    pub fn new<I>(rules: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Box<dyn ValidationRule>>,
+    {
+        CompositeVisitor {
+            visitors: rules
+                .into_iter()
+                .map(|rule| (rule.visitor(), ValidationErrorContext::new()))
+                .collect(),
+            options: ValidationOptions::unlimited(),
+            visited_nodes: 0,
+            error_count: 0,
+            started_at: Instant::now(),
+            abort_error: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: ValidationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn into_errors(self) -> Vec<ValidationError> {
+        let max_errors = self.options.max_errors;
+        let (mut errors, abort_error) = self.into_parts();
+
+        if let Some(max_errors) = max_errors {
+            errors.truncate(max_errors);
+        }
+
+        errors.extend(abort_error);
+        errors
+    }
+
+    /// The errors of all the visitors in rule order, not truncated to `max_errors`, and the
+    /// error explaining why the traversal was aborted.
+    pub(crate) fn into_parts(self) -> (Vec<ValidationError>, Option<ValidationError>) {
+        let errors = self
+            .visitors
+            .into_iter()
+            .flat_map(|(_, error_collector)| error_collector.errors)
+            .collect();
+
+        (errors, self.abort_error)
+    }
+
+    fn check_limits(&mut self, context: &mut OperationVisitorContext<'a>) {
+        let message = if self
+            .options
+            .max_errors
+            .is_some_and(|max_errors| self.error_count > max_errors)
+        {
+            TOO_MANY_ERRORS.to_string()
+        } else if let Some(max_nodes) = self
+            .options
+            .max_nodes
+            .filter(|max_nodes| self.visited_nodes > *max_nodes)
+        {
+            format!(
+                "Validation node budget of {} exceeded. Validation aborted.",
+                max_nodes
+            )
+        } else if let Some(timeout) = self
+            .options
+            .timeout
+            .filter(|timeout| self.started_at.elapsed() > *timeout)
+        {
+            format!(
+                "Validation time budget of {}ms exceeded. Validation aborted.",
+                timeout.as_millis()
+            )
+        } else {
+            return;
+        };
+
+        context.abort();
+        self.abort_error = Some(aborted(message));
+    }
+}
+
+impl<'a> OperationVisitor<'a, ()> for CompositeVisitor<'a> {
+    fn enter_document(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        document: &'a Document,
+    ) {
+        fan_out_enter!(self, enter_document, context, document);
+    }
+    fn leave_document(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        document: &Document,
+    ) {
+        fan_out!(self, leave_document, context, document);
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        operation: &'a OperationDefinition,
+    ) {
+        fan_out_enter!(self, enter_operation_definition, context, operation);
+    }
+    fn leave_operation_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        operation: &OperationDefinition,
+    ) {
+        fan_out!(self, leave_operation_definition, context, operation);
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fragment: &'a FragmentDefinition,
+    ) {
+        fan_out_enter!(self, enter_fragment_definition, context, fragment);
+    }
+    fn leave_fragment_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fragment: &FragmentDefinition,
+    ) {
+        fan_out!(self, leave_fragment_definition, context, fragment);
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        variable_definition: &'a VariableDefinition,
+    ) {
+        fan_out_enter!(
+            self,
+            enter_variable_definition,
+            context,
+            variable_definition
+        );
+    }
+    fn leave_variable_definition(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        variable_definition: &VariableDefinition,
+    ) {
+        fan_out!(
+            self,
+            leave_variable_definition,
+            context,
+            variable_definition
+        );
+    }
+
+    fn enter_directive(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        directive: &Directive,
+    ) {
+        fan_out_enter!(self, enter_directive, context, directive);
+    }
+    fn leave_directive(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        directive: &Directive,
+    ) {
+        fan_out!(self, leave_directive, context, directive);
+    }
+
+    fn enter_argument(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        argument: &'a (String, Value),
+    ) {
+        fan_out_enter!(self, enter_argument, context, argument);
+    }
+    fn leave_argument(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        argument: &(String, Value),
+    ) {
+        fan_out!(self, leave_argument, context, argument);
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        selection_set: &'a SelectionSet,
+    ) {
+        fan_out_enter!(self, enter_selection_set, context, selection_set);
+    }
+    fn leave_selection_set(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        selection_set: &SelectionSet,
+    ) {
+        fan_out!(self, leave_selection_set, context, selection_set);
+    }
+
+    fn enter_field(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        field: &Field,
+    ) {
+        fan_out_enter!(self, enter_field, context, field);
+    }
+    fn leave_field(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        field: &Field,
+    ) {
+        fan_out!(self, leave_field, context, field);
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fragment_spread: &'a FragmentSpread,
+    ) {
+        fan_out_enter!(self, enter_fragment_spread, context, fragment_spread);
+    }
+    fn leave_fragment_spread(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fragment_spread: &FragmentSpread,
+    ) {
+        fan_out!(self, leave_fragment_spread, context, fragment_spread);
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        inline_fragment: &InlineFragment,
+    ) {
+        fan_out_enter!(self, enter_inline_fragment, context, inline_fragment);
+    }
+    fn leave_inline_fragment(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        inline_fragment: &InlineFragment,
+    ) {
+        fan_out!(self, leave_inline_fragment, context, inline_fragment);
+    }
+
+    fn enter_null_value(&mut self, context: &mut OperationVisitorContext<'a>, _: &mut (), _: ()) {
+        fan_out_enter!(self, enter_null_value, context, ());
+    }
+    fn leave_null_value(&mut self, context: &mut OperationVisitorContext<'a>, _: &mut (), _: ()) {
+        fan_out!(self, leave_null_value, context, ());
+    }
+
+    fn enter_scalar_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        value: &Value,
+    ) {
+        fan_out_enter!(self, enter_scalar_value, context, value);
+    }
+    fn leave_scalar_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        value: &Value,
+    ) {
+        fan_out!(self, leave_scalar_value, context, value);
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        value: &String,
+    ) {
+        fan_out_enter!(self, enter_enum_value, context, value);
+    }
+    fn leave_enum_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        value: &String,
+    ) {
+        fan_out!(self, leave_enum_value, context, value);
+    }
+
+    fn enter_variable_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        variable_name: &'a str,
+    ) {
+        fan_out_enter!(self, enter_variable_value, context, variable_name);
+    }
+    fn leave_variable_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        variable_name: &String,
+    ) {
+        fan_out!(self, leave_variable_value, context, variable_name);
+    }
+
+    fn enter_list_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        values: &Vec<Value>,
+    ) {
+        fan_out_enter!(self, enter_list_value, context, values);
+    }
+    fn leave_list_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        values: &Vec<Value>,
+    ) {
+        fan_out!(self, leave_list_value, context, values);
+    }
+
+    fn enter_object_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fields: &BTreeMap<String, Value>,
+    ) {
+        fan_out_enter!(self, enter_object_value, context, fields);
+    }
+    fn leave_object_value(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        fields: &BTreeMap<String, Value>,
+    ) {
+        fan_out!(self, leave_object_value, context, fields);
+    }
+
+    fn enter_object_field(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        field: &(String, Value),
+    ) {
+        fan_out_enter!(self, enter_object_field, context, field);
+    }
+    fn leave_object_field(
+        &mut self,
+        context: &mut OperationVisitorContext<'a>,
+        _: &mut (),
+        field: &(String, Value),
+    ) {
+        fan_out!(self, leave_object_field, context, field);
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+use super::cost::CostOptions;
+use super::rules::*;
+use super::utils::{did_you_mean, suggestion_list};
+use super::validate::{Severity, ValidationPlan};
+
+/// A declarative `ValidationPlan`, to be deserialized from JSON, TOML, YAML...
+///
+/// ```json
+/// {
+///   "rules": {
+///     "NoUnusedFragments": { "severity": "warning" },
+///     "MaxDepth": { "maxDepth": 10, "ignoreTypename": true },
+///     "NoDeprecated": { "severity": "warning" }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ValidationConfig {
+    /// Whether the rules of `default_rules_validation_plan` are part of the plan, before the
+    /// rules configured in `rules`. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub default_rules: bool,
+    /// The rules to add or configure, by error code. Rules that are not part of the default
+    /// rules are added in the order of their error codes.
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        ValidationConfig {
+            default_rules: true,
+            rules: BTreeMap::new(),
+        }
+    }
+}
+
+/// The severity and options of a rule. Options are the other fields of the rule entry, in
+/// camelCase: `{ "severity": "error", "maxDepth": 10 }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(flatten)]
+    pub options: Map<String, JsonValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationConfigError {
+    UnknownRule {
+        name: String,
+        suggestions: Vec<String>,
+    },
+    InvalidOptions {
+        name: String,
+        reason: String,
+    },
+}
+
+impl Display for ValidationConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationConfigError::UnknownRule { name, suggestions } => {
+                write!(f, "Unknown rule \"{}\".{}", name, did_you_mean(suggestions))
+            }
+            ValidationConfigError::InvalidOptions { name, reason } => {
+                write!(f, "Invalid options for rule \"{}\": {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationConfigError {}
+
+type RuleConstructor =
+    Box<dyn Fn(&Map<String, JsonValue>) -> Result<Box<dyn ValidationRule>, String> + Send + Sync>;
+
+/// Creates rules from their error code and options, for `ValidationPlan::from_config`.
+///
+/// `RuleRegistry::default()` knows the rules of this crate, `register` adds custom ones.
+pub struct RuleRegistry {
+    constructors: HashMap<&'static str, RuleConstructor>,
+}
+
+impl RuleRegistry {
+    /// A registry without any rule.
+    pub fn new() -> Self {
+        RuleRegistry {
+            constructors: HashMap::new(),
+        }
+    }
+
+    /// Registers the rule reporting `error_code`. The constructor receives the options of the
+    /// rule entry, and returns the reason why they are invalid.
+    pub fn register<F>(&mut self, error_code: &'static str, constructor: F)
+    where
+        F: Fn(&Map<String, JsonValue>) -> Result<Box<dyn ValidationRule>, String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.constructors.insert(error_code, Box::new(constructor));
+    }
+
+    /// Registers a rule without options, rejecting any option given to it.
+    pub fn register_without_options<F>(&mut self, error_code: &'static str, constructor: F)
+    where
+        F: Fn() -> Box<dyn ValidationRule> + Send + Sync + 'static,
+    {
+        self.register(error_code, move |options| {
+            parse_options::<NoOptions>(options)?;
+            Ok(constructor())
+        });
+    }
+
+    /// Creates the rule reporting `name`.
+    pub fn create(
+        &self,
+        name: &str,
+        options: &Map<String, JsonValue>,
+    ) -> Result<Box<dyn ValidationRule>, ValidationConfigError> {
+        let constructor =
+            self.constructors
+                .get(name)
+                .ok_or_else(|| ValidationConfigError::UnknownRule {
+                    name: name.to_string(),
+                    suggestions: suggestion_list(name, self.constructors.keys()),
+                })?;
+
+        constructor(options).map_err(|reason| ValidationConfigError::InvalidOptions {
+            name: name.to_string(),
+            reason,
+        })
+    }
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        let mut registry = RuleRegistry::new();
+
+        registry.register_without_options("UniqueOperationNames", || {
+            Box::new(UniqueOperationNames::new())
+        });
+        registry.register_without_options("LoneAnonymousOperation", || {
+            Box::new(LoneAnonymousOperation::new())
+        });
+        registry.register_without_options("KnownOperationTypes", || {
+            Box::new(KnownOperationTypes::new())
+        });
+        registry.register_without_options("SingleFieldSubscriptions", || {
+            Box::new(SingleFieldSubscriptions::new())
+        });
+        registry.register_without_options("KnownTypeNames", || Box::new(KnownTypeNames::new()));
+        registry.register_without_options("FragmentsOnCompositeTypes", || {
+            Box::new(FragmentsOnCompositeTypes::new())
+        });
+        registry.register_without_options("VariablesAreInputTypes", || {
+            Box::new(VariablesAreInputTypes::new())
+        });
+        registry.register_without_options("LeafFieldSelections", || {
+            Box::new(LeafFieldSelections::new())
+        });
+        registry.register_without_options("FieldsOnCorrectType", || {
+            Box::new(FieldsOnCorrectType::new())
+        });
+        registry.register_without_options("UniqueFragmentNames", || {
+            Box::new(UniqueFragmentNames::new())
+        });
+        registry
+            .register_without_options("KnownFragmentNames", || Box::new(KnownFragmentNames::new()));
+        registry
+            .register_without_options("NoUnusedFragments", || Box::new(NoUnusedFragments::new()));
+        registry.register_without_options("OverlappingFieldsCanBeMerged", || {
+            Box::new(OverlappingFieldsCanBeMerged::new())
+        });
+        registry.register_without_options("NoFragmentsCycle", || Box::new(NoFragmentsCycle::new()));
+        registry.register_without_options("PossibleFragmentSpreads", || {
+            Box::new(PossibleFragmentSpreads::new())
+        });
+        registry
+            .register_without_options("NoUnusedVariables", || Box::new(NoUnusedVariables::new()));
+        registry.register_without_options("NoUndefinedVariables", || {
+            Box::new(NoUndefinedVariables::new())
+        });
+        registry
+            .register_without_options("KnownArgumentNames", || Box::new(KnownArgumentNames::new()));
+        registry.register_without_options("UniqueArgumentNames", || {
+            Box::new(UniqueArgumentNames::new())
+        });
+        registry.register_without_options("UniqueVariableNames", || {
+            Box::new(UniqueVariableNames::new())
+        });
+        registry.register_without_options("ProvidedRequiredArguments", || {
+            Box::new(ProvidedRequiredArguments::new())
+        });
+        registry.register_without_options("KnownDirectives", || Box::new(KnownDirectives::new()));
+        registry.register_without_options("VariablesInAllowedPosition", || {
+            Box::new(VariablesInAllowedPosition::new())
+        });
+        registry.register_without_options("UniqueDirectivesPerLocation", || {
+            Box::new(UniqueDirectivesPerLocation::new())
+        });
+        registry.register_without_options("NoDeprecated", || Box::new(NoDeprecated::new()));
+        registry.register_without_options("DeferStreamDirectiveOnRootField", || {
+            Box::new(DeferStreamDirectiveOnRootField::new())
+        });
+        registry.register_without_options("DeferStreamDirectiveOnValidOperations", || {
+            Box::new(DeferStreamDirectiveOnValidOperations::new())
+        });
+        registry.register_without_options("DeferStreamDirectiveLabel", || {
+            Box::new(DeferStreamDirectiveLabel::new())
+        });
+        registry.register_without_options("StreamDirectiveOnListField", || {
+            Box::new(StreamDirectiveOnListField::new())
+        });
+
+        registry.register("ValuesOfCorrectType", |options| {
+            let options: ValuesOfCorrectTypeOptions = parse_options(options)?;
+            let rule = ValuesOfCorrectType::new();
+
+            Ok(Box::new(if options.builtin_scalar_validators {
+                rule.with_builtin_scalar_validators()
+            } else {
+                rule
+            }))
+        });
+        registry.register("NoSchemaIntrospection", |options| {
+            let options: NoSchemaIntrospectionOptions = parse_options(options)?;
+            let rule = options
+                .allowed_fields
+                .iter()
+                .fold(NoSchemaIntrospection::new(), |rule, field_name| {
+                    rule.allow_field(field_name)
+                });
+
+            Ok(Box::new(if options.allow_typename {
+                rule
+            } else {
+                rule.disallow_typename()
+            }))
+        });
+        registry.register("MaxDepth", |options| {
+            let options: MaxDepthOptions = parse_options(options)?;
+
+            Ok(Box::new(
+                MaxDepth::new(options.max_depth)
+                    .ignore_introspection(options.ignore_introspection)
+                    .ignore_typename(options.ignore_typename),
+            ))
+        });
+        registry.register("MaxCost", |options| {
+            let options: MaxCostOptions = parse_options(options)?;
+            let defaults = CostOptions::default();
+
+            Ok(Box::new(
+                MaxCost::new(options.max_cost).with_options(CostOptions {
+                    default_composite_weight: options
+                        .default_composite_weight
+                        .unwrap_or(defaults.default_composite_weight),
+                    default_leaf_weight: options
+                        .default_leaf_weight
+                        .unwrap_or(defaults.default_leaf_weight),
+                    default_list_size: options
+                        .default_list_size
+                        .unwrap_or(defaults.default_list_size),
+                    ..defaults
+                }),
+            ))
+        });
+        registry.register("MaxAliases", |options| {
+            let options: MaxAliasesOptions = parse_options(options)?;
+            Ok(Box::new(MaxAliases::new(options.max_aliases)))
+        });
+        registry.register("MaxRootFields", |options| {
+            let options: MaxRootFieldsOptions = parse_options(options)?;
+            Ok(Box::new(MaxRootFields::new(options.max_root_fields)))
+        });
+        registry.register("MaxDirectives", |options| {
+            let options: MaxDirectivesOptions = parse_options(options)?;
+            Ok(Box::new(MaxDirectives::new(options.max_directives)))
+        });
+        registry.register("MaxFields", |options| {
+            let options: MaxFieldsOptions = parse_options(options)?;
+            Ok(Box::new(MaxFields::new(options.max_fields)))
+        });
+
+        registry
+    }
+}
+
+/// Deserializes the options of a rule entry, rejecting unknown options.
+pub fn parse_options<T: DeserializeOwned>(options: &Map<String, JsonValue>) -> Result<T, String> {
+    serde_json::from_value(JsonValue::Object(options.clone())).map_err(|e| e.to_string())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoOptions {}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ValuesOfCorrectTypeOptions {
+    #[serde(default)]
+    builtin_scalar_validators: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct NoSchemaIntrospectionOptions {
+    #[serde(default)]
+    allowed_fields: Vec<String>,
+    #[serde(default = "default_true")]
+    allow_typename: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxDepthOptions {
+    max_depth: usize,
+    #[serde(default = "default_true")]
+    ignore_introspection: bool,
+    #[serde(default)]
+    ignore_typename: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxCostOptions {
+    max_cost: f64,
+    default_composite_weight: Option<f64>,
+    default_leaf_weight: Option<f64>,
+    default_list_size: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxAliasesOptions {
+    max_aliases: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxRootFieldsOptions {
+    max_root_fields: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxDirectivesOptions {
+    max_directives: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MaxFieldsOptions {
+    max_fields: usize,
+}
+
+impl ValidationPlan {
+    /// Builds a plan from a configuration, with the rules of this crate.
+    pub fn from_config(config: &ValidationConfig) -> Result<Self, ValidationConfigError> {
+        Self::from_config_with_registry(config, &RuleRegistry::default())
+    }
+
+    /// Builds a plan from a configuration, creating its rules with `registry`.
+    pub fn from_config_with_registry(
+        config: &ValidationConfig,
+        registry: &RuleRegistry,
+    ) -> Result<Self, ValidationConfigError> {
+        let mut plan = ValidationPlan::new();
+        let empty_config = RuleConfig::default();
+
+        let default_rule_names = if config.default_rules {
+            default_rules_validation_plan()
+                .rules
+                .iter()
+                .map(|rule| rule.error_code())
+                .collect()
+        } else {
+            vec![]
+        };
+        let configured_rule_names = config
+            .rules
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !default_rule_names.contains(name));
+
+        for name in default_rule_names
+            .iter()
+            .copied()
+            .chain(configured_rule_names)
+        {
+            let rule_config = config.rules.get(name).unwrap_or(&empty_config);
+            let rule = registry.create(name, &rule_config.options)?;
+
+            plan.add_rule_with_severity(rule, rule_config.severity);
+        }
+
+        Ok(plan)
+    }
+}
+
+#[test]
+fn default_config_matches_the_default_plan() {
+    let plan = ValidationPlan::from_config(&ValidationConfig::default()).unwrap();
+
+    assert_eq!(
+        plan.fingerprint(),
+        default_rules_validation_plan().fingerprint()
+    );
+}
+
+#[test]
+fn configures_severities_and_options() {
+    use crate::validation::test_utils::*;
+
+    let config: ValidationConfig = serde_json::from_str(
+        r#"{
+          "rules": {
+            "NoUnusedVariables": { "severity": "warning" },
+            "FieldsOnCorrectType": { "severity": "off" },
+            "MaxDepth": { "maxDepth": 1 }
+          }
+        }"#,
+    )
+    .unwrap();
+    let plan = ValidationPlan::from_config(&config).unwrap();
+
+    assert_eq!(plan.rules.len(), 26);
+    assert_eq!(plan.rules[25].error_code(), "MaxDepth");
+    assert_eq!(plan.severity("NoUnusedVariables"), Severity::Warning);
+    assert_eq!(plan.severity("FieldsOnCorrectType"), Severity::Off);
+
+    let errors = test_operation_with_schema(
+        "query Foo($unused: DogCommand) { dog { name unknownField } }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Query depth limit of 1 exceeded, found 2 at \"dog.name\"."]
+    );
+}
+
+#[test]
+fn only_configured_rules() {
+    let config: ValidationConfig = serde_json::from_value(serde_json::json!({
+        "defaultRules": false,
+        "rules": {
+            "MaxFields": { "maxFields": 100 },
+            "MaxCost": { "maxCost": 1000, "defaultListSize": 10 },
+            "NoSchemaIntrospection": { "allowedFields": ["__type"], "allowTypename": false },
+            "ValuesOfCorrectType": { "builtinScalarValidators": true }
+        }
+    }))
+    .unwrap();
+    let plan = ValidationPlan::from_config(&config).unwrap();
+
+    assert_eq!(
+        plan.rules
+            .iter()
+            .map(|rule| rule.error_code())
+            .collect::<Vec<_>>(),
+        vec![
+            "MaxCost",
+            "MaxFields",
+            "NoSchemaIntrospection",
+            "ValuesOfCorrectType"
+        ]
+    );
+}
+
+#[test]
+fn reports_unknown_rules_and_invalid_options() {
+    let config = |rules: JsonValue| -> ValidationConfig {
+        serde_json::from_value(serde_json::json!({ "rules": rules })).unwrap()
+    };
+
+    let error = ValidationPlan::from_config(&config(serde_json::json!({ "MaxDeph": {} })))
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Unknown rule \"MaxDeph\". Did you mean \"MaxDepth\"?"
+    );
+
+    let error = ValidationPlan::from_config(&config(serde_json::json!({
+        "MaxDepth": { "maxDepth": 3, "ignoreFragments": true }
+    })))
+    .err()
+    .unwrap();
+    assert!(matches!(
+        error,
+        ValidationConfigError::InvalidOptions { ref name, .. } if name == "MaxDepth"
+    ));
+
+    let error = ValidationPlan::from_config(&config(serde_json::json!({
+        "KnownDirectives": { "strict": true }
+    })))
+    .err()
+    .unwrap();
+    assert!(error
+        .to_string()
+        .starts_with("Invalid options for rule \"KnownDirectives\": unknown field `strict`"));
+}
+
+#[test]
+fn custom_rules_can_be_registered() {
+    let mut registry = RuleRegistry::default();
+    registry.register("MaxRootFieldsInCi", |options| {
+        let limit = options
+            .get("limit")
+            .and_then(JsonValue::as_u64)
+            .ok_or("missing \"limit\"")?;
+
+        Ok(Box::new(MaxRootFields::new(limit as usize)))
+    });
+
+    let config: ValidationConfig = serde_json::from_value(serde_json::json!({
+        "defaultRules": false,
+        "rules": { "MaxRootFieldsInCi": { "limit": 2 } }
+    }))
+    .unwrap();
+
+    let plan = ValidationPlan::from_config_with_registry(&config, &registry).unwrap();
+    assert_eq!(plan.rules.len(), 1);
+
+    assert!(ValidationPlan::from_config(&config).is_err());
+}
@@ -0,0 +1,817 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::ast::{
+    OperationDefinitionExtension, PreparedSchema, SchemaDocumentExtension, TypeDefinitionExtension,
+    TypeExtension,
+};
+use crate::static_graphql::query::{
+    self, Definition, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+    TypeCondition, Value,
+};
+use crate::static_graphql::schema::{self, TypeDefinition};
+
+/// Weights used for the fields and lists that have no `@cost` or `@listSize` directive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostOptions {
+    /// Weight of a field returning an object, interface or union.
+    pub default_composite_weight: f64,
+    /// Weight of a field returning a scalar or an enum.
+    pub default_leaf_weight: f64,
+    /// Number of items assumed for a list field without `@listSize`, or when none of its
+    /// slicing arguments is given.
+    pub default_list_size: f64,
+    /// Maximum number of entries in `CostAnalysis::fields`. The total is always computed in
+    /// full.
+    pub max_breakdown_fields: usize,
+}
+
+impl Default for CostOptions {
+    fn default() -> Self {
+        CostOptions {
+            default_composite_weight: 1.0,
+            default_leaf_weight: 0.0,
+            default_list_size: 1.0,
+            max_breakdown_fields: 1000,
+        }
+    }
+}
+
+/// The cost of a field, including its selections, at a response path.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FieldCost {
+    pub path: Vec<String>,
+    pub cost: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CostAnalysis {
+    pub total: f64,
+    /// The cost of every response path of the operation, in document order. Fields selected at
+    /// the same path by several selections or fragment spreads are added up.
+    pub fields: Vec<FieldCost>,
+    /// Whether paths were left out of `fields`, see `CostOptions::max_breakdown_fields`.
+    pub truncated: bool,
+}
+
+/// Estimates the cost of an operation of `document`, the one named `operation_name` or the only
+/// one, from the `@cost` and `@listSize` directives of the schema.
+///
+/// The cost of a field is the weight of its arguments plus, for each returned item, the weight
+/// of the field and the cost of its selections:
+///
+/// - the weight of a field comes from `@cost(weight:)` on its definition, or on its return type,
+///   or from `CostOptions`;
+/// - the number of items of a list is the largest of the `slicingArguments` given to the field,
+///   from literals or `variables`, or `assumedSize`. With `sizedFields`, the number of items
+///   applies to these child fields instead. Negative sizes count as no items.
+///
+/// Without `variables`, the size given by a variable is unknown: the list counts as
+/// `assumedSize` items, or as an unbounded number of items (an infinite cost) without it. With
+/// `variables`, a missing variable uses its default value.
+///
+/// Fragments count where they are spread, so the result is an upper bound when the same fields
+/// are selected more than once. The cost of each fragment is computed once.
+pub fn analyze_cost(
+    schema: &PreparedSchema,
+    document: &query::Document,
+    operation_name: Option<&str>,
+    variables: Option<&Map<String, JsonValue>>,
+    options: &CostOptions,
+) -> Option<CostAnalysis> {
+    let mut operations = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        });
+    let operation = match operation_name {
+        Some(name) => operations.find(|operation| operation_name_of(operation) == Some(name)),
+        None => operations.next().filter(|_| operations.next().is_none()),
+    }?;
+
+    let known_fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    Some(analyze_operation_cost(
+        schema,
+        operation,
+        &known_fragments,
+        variables,
+        options,
+    ))
+}
+
+fn operation_name_of(operation: &OperationDefinition) -> Option<&str> {
+    match operation {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(query) => query.name.as_deref(),
+        OperationDefinition::Mutation(mutation) => mutation.name.as_deref(),
+        OperationDefinition::Subscription(subscription) => subscription.name.as_deref(),
+    }
+}
+
+/// Same as `analyze_cost`, for an operation and the fragments it may spread.
+pub fn analyze_operation_cost<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a OperationDefinition,
+    known_fragments: &HashMap<&'a str, &'a FragmentDefinition>,
+    variables: Option<&'a Map<String, JsonValue>>,
+    options: &'a CostOptions,
+) -> CostAnalysis {
+    let root_type = schema.root_operation_type(operation);
+
+    let mut analyzer = CostAnalyzer {
+        schema,
+        operation,
+        known_fragments,
+        variables,
+        options,
+        path: vec![],
+        visiting_fragments: HashSet::new(),
+        fragments: HashMap::new(),
+        breakdown: Breakdown::default(),
+    };
+
+    let total = match root_type {
+        Some(root_type) => {
+            analyzer.selection_set_cost(operation.selection_set(), &root_type.name, None)
+        }
+        None => 0.0,
+    };
+
+    CostAnalysis {
+        total,
+        fields: analyzer.breakdown.fields,
+        truncated: analyzer.breakdown.truncated,
+    }
+}
+
+struct CostAnalyzer<'a, 'f> {
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a OperationDefinition,
+    known_fragments: &'f HashMap<&'a str, &'a FragmentDefinition>,
+    variables: Option<&'a Map<String, JsonValue>>,
+    options: &'a CostOptions,
+    path: Vec<String>,
+    visiting_fragments: HashSet<&'a str>,
+    fragments: HashMap<&'a str, Rc<FragmentCost<'a>>>,
+    breakdown: Breakdown,
+}
+
+/// The cost of the fields selected by a fragment, computed once per fragment.
+struct FragmentCost<'a> {
+    /// The cost of the root fields of the fragment by name, for `@listSize(sizedFields:)`.
+    root_fields: Vec<(&'a str, f64)>,
+    /// The paths of the fragment, relative to where it's spread.
+    breakdown: Breakdown,
+}
+
+#[derive(Default)]
+struct Breakdown {
+    fields: Vec<FieldCost>,
+    indexes: HashMap<Vec<String>, usize>,
+    truncated: bool,
+}
+
+impl Breakdown {
+    /// The index of a path, added unless the breakdown already has `limit` paths.
+    fn entry(&mut self, path: &[String], limit: usize) -> Option<usize> {
+        if let Some(index) = self.indexes.get(path) {
+            return Some(*index);
+        }
+
+        if self.fields.len() >= limit {
+            self.truncated = true;
+            return None;
+        }
+
+        self.indexes.insert(path.to_vec(), self.fields.len());
+        self.fields.push(FieldCost {
+            path: path.to_vec(),
+            cost: 0.0,
+        });
+
+        Some(self.fields.len() - 1)
+    }
+}
+
+/// The list size applied to the child fields named in `@listSize(sizedFields:)`.
+type SizedFields<'s> = Option<(&'s [&'s str], f64)>;
+
+/// The cost of `size` items, free when there are no items or when items are free, even if the
+/// size is unbounded.
+fn scale(size: f64, cost: f64) -> f64 {
+    if size == 0.0 || cost == 0.0 {
+        0.0
+    } else {
+        size * cost
+    }
+}
+
+fn add_root_field<'a>(root_fields: &mut Vec<(&'a str, f64)>, name: &'a str, cost: f64) {
+    match root_fields
+        .iter_mut()
+        .find(|(field_name, _)| *field_name == name)
+    {
+        Some((_, field_cost)) => *field_cost += cost,
+        None => root_fields.push((name, cost)),
+    }
+}
+
+impl<'a, 'f> CostAnalyzer<'a, 'f> {
+    fn selection_set_cost(
+        &mut self,
+        selection_set: &'a SelectionSet,
+        parent_type_name: &str,
+        sized_fields: SizedFields,
+    ) -> f64 {
+        let mut root_fields = vec![];
+        self.collect_root_fields(selection_set, parent_type_name, &mut root_fields);
+
+        root_fields
+            .into_iter()
+            .map(|(name, cost)| match sized_fields {
+                Some((names, size)) if names.contains(&name) => scale(size, cost),
+                _ => cost,
+            })
+            .sum()
+    }
+
+    /// Adds the cost of the fields of a selection set, and of its fragments, by field name.
+    fn collect_root_fields(
+        &mut self,
+        selection_set: &'a SelectionSet,
+        parent_type_name: &str,
+        root_fields: &mut Vec<(&'a str, f64)>,
+    ) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => {
+                    let cost = self.field_cost(field, parent_type_name);
+                    add_root_field(root_fields, &field.name, cost);
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let type_name = match &inline_fragment.type_condition {
+                        Some(TypeCondition::On(type_name)) => type_name.as_str(),
+                        None => parent_type_name,
+                    };
+
+                    self.collect_root_fields(&inline_fragment.selection_set, type_name, root_fields)
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let Some(fragment) = self.fragment_cost(&fragment_spread.fragment_name) else {
+                        continue;
+                    };
+
+                    for (name, cost) in &fragment.root_fields {
+                        add_root_field(root_fields, name, *cost);
+                    }
+
+                    for field in &fragment.breakdown.fields {
+                        let path = [self.path.as_slice(), field.path.as_slice()].concat();
+
+                        if let Some(index) = self
+                            .breakdown
+                            .entry(&path, self.options.max_breakdown_fields)
+                        {
+                            self.breakdown.fields[index].cost += field.cost;
+                        }
+                    }
+                    self.breakdown.truncated |= fragment.breakdown.truncated;
+                }
+            }
+        }
+    }
+
+    /// The cost of a fragment, `None` for unknown fragments and fragment cycles.
+    fn fragment_cost(&mut self, fragment_name: &'a str) -> Option<Rc<FragmentCost<'a>>> {
+        if let Some(fragment_cost) = self.fragments.get(fragment_name) {
+            return Some(fragment_cost.clone());
+        }
+
+        let fragment = *self.known_fragments.get(fragment_name)?;
+        if !self.visiting_fragments.insert(fragment_name) {
+            return None;
+        }
+
+        let TypeCondition::On(type_name) = &fragment.type_condition;
+        let path = std::mem::take(&mut self.path);
+        let breakdown = std::mem::take(&mut self.breakdown);
+        let mut root_fields = vec![];
+        self.collect_root_fields(&fragment.selection_set, type_name, &mut root_fields);
+
+        let fragment_cost = Rc::new(FragmentCost {
+            root_fields,
+            breakdown: std::mem::replace(&mut self.breakdown, breakdown),
+        });
+        self.path = path;
+        self.visiting_fragments.remove(fragment_name);
+        self.fragments.insert(fragment_name, fragment_cost.clone());
+
+        Some(fragment_cost)
+    }
+
+    fn field_cost(&mut self, field: &'a Field, parent_type_name: &str) -> f64 {
+        // Introspection is not priced, and unknown fields are reported by `FieldsOnCorrectType`.
+        let field_def = match self.schema.field_by_name(parent_type_name, &field.name) {
+            Some(field_def) if !field.name.starts_with("__") => field_def,
+            _ => return 0.0,
+        };
+        let return_type_name = field_def.field_type.inner_type();
+        let return_type = self.schema.type_by_name(return_type_name);
+
+        self.path
+            .push(field.alias.as_ref().unwrap_or(&field.name).clone());
+        let index = self
+            .breakdown
+            .entry(&self.path, self.options.max_breakdown_fields);
+
+        let arguments_weight: f64 = field
+            .arguments
+            .iter()
+            .filter_map(|(name, _)| field_def.arguments.iter().find(|arg| &arg.name == name))
+            .filter_map(|argument_def| cost_weight(&argument_def.directives))
+            .sum();
+
+        let weight = cost_weight(&field_def.directives)
+            .or_else(|| return_type.and_then(type_cost_weight))
+            .unwrap_or(if return_type.is_composite_type() {
+                self.options.default_composite_weight
+            } else {
+                self.options.default_leaf_weight
+            });
+
+        let list_size = directive_by_name(&field_def.directives, "listSize");
+        let sized_fields = list_size
+            .and_then(|list_size| directive_argument(list_size, "sizedFields"))
+            .map(string_list)
+            .unwrap_or_default();
+
+        let cost = if !sized_fields.is_empty() {
+            let size = self.list_size(field, list_size);
+
+            arguments_weight
+                + weight
+                + self.selection_set_cost(
+                    &field.selection_set,
+                    return_type_name,
+                    Some((&sized_fields, size)),
+                )
+        } else {
+            let size = if is_list(&field_def.field_type) {
+                self.list_size(field, list_size)
+            } else {
+                1.0
+            };
+
+            arguments_weight
+                + scale(
+                    size,
+                    weight + self.selection_set_cost(&field.selection_set, return_type_name, None),
+                )
+        };
+
+        if let Some(index) = index {
+            self.breakdown.fields[index].cost += cost;
+        }
+        self.path.pop();
+
+        cost
+    }
+
+    fn list_size(&self, field: &Field, list_size: Option<&schema::Directive>) -> f64 {
+        let Some(list_size) = list_size else {
+            return self.options.default_list_size;
+        };
+
+        let mut unknown_size = false;
+        let sliced_size = directive_argument(list_size, "slicingArguments")
+            .map(string_list)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|name| {
+                let (_, value) = field.arguments.iter().find(|(arg, _)| arg == name)?;
+                let size = self.int_value(value);
+                unknown_size |= size.is_none() && self.is_unknown_variable(value);
+                size
+            })
+            .map(|size| size.max(0.0))
+            .reduce(f64::max);
+        let assumed_size = match directive_argument(list_size, "assumedSize") {
+            Some(Value::Int(size)) => size.as_i64().map(|size| (size as f64).max(0.0)),
+            _ => None,
+        };
+
+        if unknown_size {
+            // The variable may be anything, `assumedSize` is the only bound.
+            return match (sliced_size, assumed_size) {
+                (Some(sliced_size), Some(assumed_size)) => sliced_size.max(assumed_size),
+                (None, Some(assumed_size)) => assumed_size,
+                (_, None) => f64::INFINITY,
+            };
+        }
+
+        sliced_size
+            .or(assumed_size)
+            .unwrap_or(self.options.default_list_size)
+    }
+
+    /// Whether a value is a variable while the variables are not known.
+    fn is_unknown_variable(&self, value: &Value) -> bool {
+        matches!(value, Value::Variable(_)) && self.variables.is_none()
+    }
+
+    /// The value of an `Int` argument, or of the variable it refers to.
+    fn int_value(&self, value: &Value) -> Option<f64> {
+        match value {
+            Value::Int(number) => number.as_i64().map(|number| number as f64),
+            Value::Variable(name) => {
+                let variables = self.variables?;
+
+                if let Some(value) = variables.get(name) {
+                    return value.as_f64();
+                }
+
+                self.operation
+                    .variable_definitions()
+                    .iter()
+                    .find(|variable| &variable.name == name)
+                    .and_then(|variable| variable.default_value.as_ref())
+                    .and_then(|value| self.int_value(value))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn directive_by_name<'d>(
+    directives: &'d [schema::Directive],
+    name: &str,
+) -> Option<&'d schema::Directive> {
+    directives.iter().find(|directive| directive.name == name)
+}
+
+fn directive_argument<'d>(directive: &'d schema::Directive, name: &str) -> Option<&'d Value> {
+    directive
+        .arguments
+        .iter()
+        .find(|(argument, _)| argument == name)
+        .map(|(_, value)| value)
+}
+
+fn string_list(value: &Value) -> Vec<&str> {
+    match value {
+        Value::List(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::String(item) => Some(item.as_str()),
+                _ => None,
+            })
+            .collect(),
+        Value::String(item) => vec![item.as_str()],
+        _ => vec![],
+    }
+}
+
+fn is_list(field_type: &schema::Type) -> bool {
+    match field_type {
+        schema::Type::NonNullType(of_type) => is_list(of_type),
+        field_type => field_type.is_list_type(),
+    }
+}
+
+/// The weight of a `@cost(weight:)` directive, given as a string per the cost directive spec,
+/// or as a number.
+fn cost_weight(directives: &[schema::Directive]) -> Option<f64> {
+    match directive_argument(directive_by_name(directives, "cost")?, "weight")? {
+        Value::String(weight) => weight.parse().ok(),
+        Value::Int(weight) => weight.as_i64().map(|weight| weight as f64),
+        Value::Float(weight) => Some(*weight),
+        _ => None,
+    }
+}
+
+fn type_cost_weight(type_def: &TypeDefinition) -> Option<f64> {
+    match type_def {
+        TypeDefinition::Object(object) => cost_weight(&object.directives),
+        TypeDefinition::Scalar(scalar) => cost_weight(&scalar.directives),
+        TypeDefinition::Enum(enum_type) => cost_weight(&enum_type.directives),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub static COST_TEST_SCHEMA: &str = r#"
+directive @cost(weight: String!) on ARGUMENT_DEFINITION | ENUM | FIELD_DEFINITION | INPUT_FIELD_DEFINITION | OBJECT | SCALAR
+directive @listSize(assumedSize: Int, slicingArguments: [String!], sizedFields: [String!], requireOneSlicingArgument: Boolean = true) on FIELD_DEFINITION
+
+type Query {
+  user(id: ID!): User
+  users(first: Int, last: Int): [User!]! @listSize(slicingArguments: ["first", "last"], assumedSize: 50)
+  everyone: [User]
+  search(term: String @cost(weight: "3")): [Post] @listSize(assumedSize: 5) @cost(weight: "2")
+  feed(first: Int): PostConnection @listSize(slicingArguments: ["first"], sizedFields: ["edges"])
+}
+
+type User {
+  id: ID!
+  name: String
+  friends(first: Int): [User] @listSize(slicingArguments: ["first"])
+  posts: [Post]
+}
+
+type Post @cost(weight: "4") {
+  title: String
+}
+
+type PostConnection {
+  edges: [PostEdge]
+}
+
+type PostEdge {
+  node: Post
+}
+"#;
+
+#[cfg(test)]
+fn analyze_test_cost(operation: &str, variables: serde_json::Value) -> Option<CostAnalysis> {
+    let schema = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>(COST_TEST_SCHEMA)
+            .unwrap()
+            .into_static(),
+    );
+    let document = crate::parser::parse_query::<String>(operation)
+        .unwrap()
+        .into_static();
+
+    analyze_cost(
+        &schema,
+        &document,
+        None,
+        variables.as_object(),
+        &CostOptions::default(),
+    )
+}
+
+#[cfg(test)]
+fn field_costs(analysis: &CostAnalysis) -> Vec<(String, f64)> {
+    analysis
+        .fields
+        .iter()
+        .map(|field| (field.path.join("."), field.cost))
+        .collect()
+}
+
+#[test]
+fn breakdown_per_response_path() {
+    let analysis = analyze_test_cost(
+        "{
+          users(first: 2) {
+            name
+            friends(first: 3) {
+              id
+            }
+          }
+        }",
+        serde_json::Value::Null,
+    )
+    .unwrap();
+
+    assert_eq!(analysis.total, 8.0);
+    assert_eq!(
+        field_costs(&analysis),
+        vec![
+            ("users".to_string(), 8.0),
+            ("users.name".to_string(), 0.0),
+            ("users.friends".to_string(), 3.0),
+            ("users.friends.id".to_string(), 0.0),
+        ]
+    );
+}
+
+#[test]
+fn list_sizes_from_slicing_arguments() {
+    use serde_json::json;
+
+    let operation = "query ($first: Int, $last: Int = 3) {
+      users(first: $first, last: $last) {
+        id
+      }
+    }";
+
+    assert_eq!(
+        analyze_test_cost(operation, json!({ "first": 7 }))
+            .unwrap()
+            .total,
+        7.0
+    );
+    assert_eq!(analyze_test_cost(operation, json!({})).unwrap().total, 3.0);
+    assert_eq!(
+        analyze_test_cost(operation, json!({ "last": null }))
+            .unwrap()
+            .total,
+        50.0
+    );
+    assert_eq!(
+        analyze_test_cost("{ everyone { id } }", serde_json::Value::Null)
+            .unwrap()
+            .total,
+        1.0
+    );
+}
+
+#[test]
+fn weights_from_cost_directives() {
+    let analysis = analyze_test_cost(
+        "{
+          search(term: \"graphql\") {
+            title
+          }
+          user(id: 1) {
+            posts {
+              title
+            }
+          }
+        }",
+        serde_json::Value::Null,
+    )
+    .unwrap();
+
+    assert_eq!(
+        field_costs(&analysis),
+        vec![
+            ("search".to_string(), 13.0),
+            ("search.title".to_string(), 0.0),
+            ("user".to_string(), 5.0),
+            ("user.posts".to_string(), 4.0),
+            ("user.posts.title".to_string(), 0.0),
+        ]
+    );
+    assert_eq!(analysis.total, 18.0);
+}
+
+#[test]
+fn sized_fields_and_fragments() {
+    let analysis = analyze_test_cost(
+        "{
+          latest: feed(first: 10) {
+            ...Feed
+          }
+        }
+        fragment Feed on PostConnection {
+          edges {
+            ... on PostEdge {
+              node {
+                title
+              }
+            }
+          }
+          ...Feed
+        }",
+        serde_json::Value::Null,
+    )
+    .unwrap();
+
+    assert_eq!(
+        field_costs(&analysis),
+        vec![
+            ("latest".to_string(), 51.0),
+            ("latest.edges".to_string(), 5.0),
+            ("latest.edges.node".to_string(), 4.0),
+            ("latest.edges.node.title".to_string(), 0.0),
+        ]
+    );
+}
+
+#[test]
+fn selects_the_operation() {
+    let schema = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>(COST_TEST_SCHEMA)
+            .unwrap()
+            .into_static(),
+    );
+    let document = crate::parser::parse_query::<String>(
+        "query One { user(id: 1) { id } } query Many { users { id } }",
+    )
+    .unwrap()
+    .into_static();
+    let cost = |operation_name| {
+        analyze_cost(
+            &schema,
+            &document,
+            operation_name,
+            None,
+            &CostOptions::default(),
+        )
+        .map(|analysis| analysis.total)
+    };
+
+    assert_eq!(cost(Some("One")), Some(1.0));
+    assert_eq!(cost(Some("Many")), Some(50.0));
+    assert_eq!(cost(Some("Unknown")), None);
+    assert_eq!(cost(None), None);
+}
+
+#[test]
+fn negative_list_sizes() {
+    let analysis = analyze_test_cost(
+        "{
+          users(first: -100) {
+            friends(first: -3) {
+              id
+            }
+          }
+        }",
+        serde_json::Value::Null,
+    )
+    .unwrap();
+
+    assert_eq!(analysis.total, 0.0);
+}
+
+#[test]
+fn list_sizes_from_unknown_variables() {
+    use serde_json::json;
+
+    let operation = "query ($first: Int = 3) {
+      users(first: $first) {
+        friends(first: $first) {
+          id
+        }
+      }
+    }";
+
+    assert_eq!(analyze_test_cost(operation, json!({})).unwrap().total, 12.0);
+    assert_eq!(
+        analyze_test_cost(operation, serde_json::Value::Null)
+            .unwrap()
+            .total,
+        f64::INFINITY
+    );
+    assert_eq!(
+        analyze_test_cost(
+            "query ($first: Int) { users(first: $first) { id } }",
+            serde_json::Value::Null
+        )
+        .unwrap()
+        .total,
+        50.0
+    );
+}
+
+#[test]
+fn fragments_are_analyzed_once() {
+    let fragments = (0..40)
+        .map(|i| {
+            format!(
+                "fragment F{} on User {{ a: friends(first: 1) {{ ...F{} }} b: friends(first: 1) {{ ...F{} }} }}",
+                i,
+                i + 1,
+                i + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let operation = format!(
+        "{{ user(id: 1) {{ ...F0 }} }} {} fragment F40 on User {{ id }}",
+        fragments
+    );
+
+    let analysis = analyze_test_cost(&operation, serde_json::Value::Null).unwrap();
+
+    assert!(analysis.total > 1e12);
+    assert_eq!(analysis.fields.len(), 1000);
+    assert!(analysis.truncated);
+
+    let doubled = (0..40)
+        .map(|i| format!("fragment F{} on User {{ ...F{} ...F{} }}", i, i + 1, i + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let operation = format!(
+        "{{ user(id: 1) {{ ...F0 }} }} {} fragment F40 on User {{ posts {{ title }} }}",
+        doubled
+    );
+
+    let analysis = analyze_test_cost(&operation, serde_json::Value::Null).unwrap();
+
+    assert_eq!(analysis.total, 1.0 + 4.0 * 2f64.powi(40));
+    assert_eq!(
+        field_costs(&analysis),
+        vec![
+            ("user".to_string(), 1.0 + 4.0 * 2f64.powi(40)),
+            ("user.posts".to_string(), 4.0 * 2f64.powi(40)),
+            ("user.posts.title".to_string(), 0.0),
+        ]
+    );
+}
@@ -1,6 +1,13 @@
+pub mod cache;
+pub mod composite_visitor;
+pub mod config;
+pub mod cost;
 pub mod rules;
+pub mod scalars;
+pub mod trusted_documents;
 pub mod utils;
 pub mod validate;
+pub mod variables;
 
 #[cfg(test)]
 pub mod test_utils;
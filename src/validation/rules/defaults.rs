@@ -2,19 +2,20 @@ use crate::validation::validate::ValidationPlan;
 
 use super::{
     FieldsOnCorrectType, FragmentsOnCompositeTypes, KnownArgumentNames, KnownDirectives,
-    KnownFragmentNames, KnownTypeNames, LeafFieldSelections, LoneAnonymousOperation,
-    NoFragmentsCycle, NoUndefinedVariables, NoUnusedFragments, NoUnusedVariables,
-    OverlappingFieldsCanBeMerged, PossibleFragmentSpreads, ProvidedRequiredArguments,
-    SingleFieldSubscriptions, UniqueArgumentNames, UniqueDirectivesPerLocation,
-    UniqueFragmentNames, UniqueOperationNames, UniqueVariableNames, ValuesOfCorrectType,
-    VariablesAreInputTypes, VariablesInAllowedPosition,
+    KnownFragmentNames, KnownOperationTypes, KnownTypeNames, LeafFieldSelections,
+    LoneAnonymousOperation, NoFragmentsCycle, NoUndefinedVariables, NoUnusedFragments,
+    NoUnusedVariables, OverlappingFieldsCanBeMerged, PossibleFragmentSpreads,
+    ProvidedRequiredArguments, SingleFieldSubscriptions, UniqueArgumentNames,
+    UniqueDirectivesPerLocation, UniqueFragmentNames, UniqueOperationNames, UniqueVariableNames,
+    ValuesOfCorrectType, VariablesAreInputTypes, VariablesInAllowedPosition,
 };
 
 pub fn default_rules_validation_plan() -> ValidationPlan {
-    let mut plan = ValidationPlan { rules: vec![] };
+    let mut plan = ValidationPlan::new();
 
     plan.add_rule(Box::new(UniqueOperationNames::new()));
     plan.add_rule(Box::new(LoneAnonymousOperation::new()));
+    plan.add_rule(Box::new(KnownOperationTypes::new()));
     plan.add_rule(Box::new(SingleFieldSubscriptions::new()));
     plan.add_rule(Box::new(KnownTypeNames::new()));
     plan.add_rule(Box::new(FragmentsOnCompositeTypes::new()));
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::parser::Pos;
+use crate::static_graphql::query::{Directive, Value};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Defer and stream directive labels
+///
+/// A GraphQL document is only valid if the `label` arguments of `@defer` and `@stream` are
+/// static strings, unique across the document.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/DeferStreamDirectiveLabelRule.ts
+pub struct DeferStreamDirectiveLabel {
+    known_labels: HashMap<String, Pos>,
+}
+
+impl Default for DeferStreamDirectiveLabel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeferStreamDirectiveLabel {
+    pub fn new() -> Self {
+        DeferStreamDirectiveLabel {
+            known_labels: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for DeferStreamDirectiveLabel {
+    fn enter_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        if directive.name != "defer" && directive.name != "stream" {
+            return;
+        }
+
+        let Some((_, label)) = directive.arguments.iter().find(|(name, _)| name == "label") else {
+            return;
+        };
+
+        let details = ValidationErrorDetails {
+            directive_name: Some(directive.name.clone()),
+            argument_name: Some("label".to_string()),
+            ..Default::default()
+        };
+
+        let Value::String(label) = label else {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![directive.position],
+                message: format!(
+                    "Argument \"@{}(label:)\" must be a static string.",
+                    directive.name
+                ),
+                details,
+            });
+            return;
+        };
+
+        match self.known_labels.get(label) {
+            Some(known_position) => user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![*known_position, directive.position],
+                message: "Value for arguments \"defer(label:)\" and \"stream(label:)\" must be unique across all Defer/Stream directive usages.".to_string(),
+                details,
+            }),
+            None => {
+                self.known_labels.insert(label.clone(), directive.position);
+            }
+        }
+    }
+}
+
+impl ValidationRule for DeferStreamDirectiveLabel {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveLabel"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(DeferStreamDirectiveLabel::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[test]
+fn unique_static_labels() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    let errors = test_operation_with_schema(
+        "{
+          ... @defer(label: \"message\") {
+            message {
+              body
+            }
+          }
+          ... @defer {
+            messages @stream(label: \"messages\") {
+              body
+            }
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn duplicate_labels() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    let errors = test_operation_with_schema(
+        "{
+          ...MessageFragment @defer(label: \"label\")
+          messages @stream(label: \"label\") {
+            body
+          }
+        }
+        fragment MessageFragment on Query {
+          message {
+            body
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Value for arguments \"defer(label:)\" and \"stream(label:)\" must be unique across all Defer/Stream directive usages."]
+    );
+    assert_eq!(errors[0].locations.len(), 2);
+}
+
+#[test]
+fn dynamic_labels() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    let errors = test_operation_with_schema(
+        "query ($label: String) {
+          ... @defer(label: $label) {
+            message {
+              body
+            }
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Argument \"@defer(label:)\" must be a static string."]
+    );
+}
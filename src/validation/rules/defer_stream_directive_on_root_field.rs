@@ -0,0 +1,173 @@
+use super::ValidationRule;
+use crate::ast::{
+    OperationVisitor, OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
+};
+use crate::static_graphql::query::Directive;
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Defer and stream directives on root field
+///
+/// A GraphQL document is only valid if `@defer` and `@stream` are not used on the root fields of
+/// mutations and subscriptions, which are executed serially or produce a single event.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/DeferStreamDirectiveOnRootFieldRule.ts
+pub struct DeferStreamDirectiveOnRootField;
+
+impl Default for DeferStreamDirectiveOnRootField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeferStreamDirectiveOnRootField {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for DeferStreamDirectiveOnRootField {
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        let directive_label = match directive.name.as_str() {
+            "defer" => "Defer",
+            "stream" => "Stream",
+            _ => return,
+        };
+        let Some(parent_type) = visitor_context.current_parent_type() else {
+            return;
+        };
+
+        let schema = visitor_context.schema;
+        let is_parent_type = |root_type_name: Option<&String>| {
+            root_type_name.map(|name| name.as_str()) == Some(parent_type.name())
+        };
+
+        let root_operation = if is_parent_type(schema.mutation_type().map(|t| &t.name)) {
+            "mutation"
+        } else if is_parent_type(schema.subscription_type().map(|t| &t.name)) {
+            "subscription"
+        } else {
+            return;
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code().into(),
+            locations: vec![directive.position],
+            message: format!(
+                "{} directive cannot be used on root {} type \"{}\".",
+                directive_label,
+                root_operation,
+                parent_type.name()
+            ),
+            details: ValidationErrorDetails {
+                directive_name: Some(directive.name.clone()),
+                type_name: Some(parent_type.name().to_string()),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+impl ValidationRule for DeferStreamDirectiveOnRootField {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveOnRootField"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(DeferStreamDirectiveOnRootField::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[test]
+fn defer_and_stream_on_query_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          ... @defer {
+            message {
+              body
+            }
+          }
+          messages @stream {
+            body
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn defer_on_nested_fields_of_subscriptions() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    let errors = test_operation_with_schema(
+        "subscription {
+          subscriptionField {
+            ... @defer {
+              body
+            }
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn defer_and_stream_on_root_mutation_and_subscription_fields() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    let errors = test_operation_with_schema(
+        "mutation {
+          ...MutationFragment @defer
+          mutationListField @stream
+        }
+        fragment MutationFragment on Mutation {
+          mutationField
+        }
+        subscription {
+          ... @defer {
+            subscriptionField {
+              body
+            }
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Defer directive cannot be used on root mutation type \"Mutation\".",
+            "Stream directive cannot be used on root mutation type \"Mutation\".",
+            "Defer directive cannot be used on root subscription type \"Subscription\".",
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 3,
+            column: 29
+        }]
+    );
+    assert_eq!(errors[2].details.directive_name, Some("defer".to_string()));
+}
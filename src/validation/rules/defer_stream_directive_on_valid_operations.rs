@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, Value,
+};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Defer and stream directives on valid operations
+///
+/// A GraphQL document is only valid if `@defer` and `@stream` are disabled in subscriptions,
+/// including in the fragments they use, by an `if` argument that can be `false`.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/DeferStreamDirectiveOnValidOperationsRule.ts
+pub struct DeferStreamDirectiveOnValidOperations<'a> {
+    subscription_fragments: HashSet<&'a str>,
+    in_subscription: bool,
+}
+
+impl<'a> Default for DeferStreamDirectiveOnValidOperations<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DeferStreamDirectiveOnValidOperations<'a> {
+    pub fn new() -> Self {
+        DeferStreamDirectiveOnValidOperations {
+            subscription_fragments: HashSet::new(),
+            in_subscription: false,
+        }
+    }
+
+    fn collect_fragments(
+        &mut self,
+        selection_set: &'a SelectionSet,
+        visitor_context: &OperationVisitorContext<'a>,
+    ) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => {
+                    self.collect_fragments(&field.selection_set, visitor_context)
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.collect_fragments(&inline_fragment.selection_set, visitor_context)
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if let Some(fragment) = visitor_context
+                        .known_fragments
+                        .get(fragment_spread.fragment_name.as_str())
+                    {
+                        if self.subscription_fragments.insert(&fragment.name) {
+                            self.collect_fragments(&fragment.selection_set, visitor_context);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether the `if` argument of a directive is a variable or `false`.
+fn can_be_disabled(directive: &Directive) -> bool {
+    directive
+        .arguments
+        .iter()
+        .find(|(name, _)| name == "if")
+        .map(|(_, value)| matches!(value, Value::Boolean(false) | Value::Variable(_)))
+        .unwrap_or(false)
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext>
+    for DeferStreamDirectiveOnValidOperations<'a>
+{
+    fn enter_document(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        document: &'a Document,
+    ) {
+        for definition in &document.definitions {
+            if let Definition::Operation(OperationDefinition::Subscription(subscription)) =
+                definition
+            {
+                self.collect_fragments(&subscription.selection_set, visitor_context);
+            }
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        self.in_subscription = matches!(operation, OperationDefinition::Subscription(_));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        fragment: &'a FragmentDefinition,
+    ) {
+        self.in_subscription = self.subscription_fragments.contains(fragment.name.as_str());
+    }
+
+    fn enter_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        if !self.in_subscription || can_be_disabled(directive) {
+            return;
+        }
+
+        let directive_label = match directive.name.as_str() {
+            "defer" => "Defer",
+            "stream" => "Stream",
+            _ => return,
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code().into(),
+            locations: vec![directive.position],
+            message: format!(
+                "{} directive not supported on subscription operations. Disable `@{}` by setting the `if` argument to `false`.",
+                directive_label, directive.name
+            ),
+            details: ValidationErrorDetails {
+                directive_name: Some(directive.name.clone()),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+impl<'v> ValidationRule for DeferStreamDirectiveOnValidOperations<'v> {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveOnValidOperations"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(DeferStreamDirectiveOnValidOperations::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[test]
+fn defer_and_stream_in_queries_and_disabled_in_subscriptions() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnValidOperations::new()));
+    let errors = test_operation_with_schema(
+        "query {
+          ...MessageFragment @defer
+        }
+        subscription ($enabled: Boolean!) {
+          subscriptionListField @stream(if: false) {
+            ... @defer(if: $enabled) {
+              body
+            }
+          }
+        }
+        fragment MessageFragment on Query {
+          messages @stream {
+            body
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn defer_and_stream_in_subscriptions() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnValidOperations::new()));
+    let errors = test_operation_with_schema(
+        "fragment BodyFragment on Message {
+          body
+        }
+        fragment MessageFragment on Message {
+          ...BodyFragment @defer(if: true)
+        }
+        subscription {
+          subscriptionListField @stream {
+            ...MessageFragment
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Defer directive not supported on subscription operations. Disable `@defer` by setting the `if` argument to `false`.",
+            "Stream directive not supported on subscription operations. Disable `@stream` by setting the `if` argument to `false`.",
+        ]
+    );
+}
@@ -1,7 +1,15 @@
+use std::collections::HashMap;
+
 use crate::ast::ext::TypeDefinitionExtension;
-use crate::ast::{visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext};
+use crate::ast::{
+    OperationVisitor, OperationVisitorContext, PreparedSchema, SchemaDocumentExtension,
+};
 use crate::static_graphql::query::{Field, OperationDefinition, Selection};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::static_graphql::schema::TypeDefinition;
+use crate::validation::utils::{
+    did_you_mean, did_you_mean_with_message, suggestion_list, ValidationError,
+    ValidationErrorContext, ValidationErrorDetails,
+};
 
 use super::ValidationRule;
 
@@ -25,6 +33,78 @@ impl FieldsOnCorrectType {
     }
 }
 
+/// For an abstract parent type, the possible types and interfaces that define the field, the
+/// ones defining it for most of the possible types first, then super types before sub types.
+fn suggested_type_names(
+    schema: &PreparedSchema,
+    parent_type: &TypeDefinition,
+    field_name: &str,
+) -> Vec<String> {
+    let mut suggested_types: Vec<&str> = vec![];
+    let mut usage_count: HashMap<&str, usize> = HashMap::new();
+
+    for possible_type in schema.possible_types(parent_type) {
+        if schema
+            .field_by_name(&possible_type.name, field_name)
+            .is_none()
+        {
+            continue;
+        }
+
+        suggested_types.push(&possible_type.name);
+        usage_count.insert(&possible_type.name, 1);
+
+        for interface in &possible_type.implements_interfaces {
+            if schema.field_by_name(interface, field_name).is_none() {
+                continue;
+            }
+
+            let count = usage_count.entry(interface).or_insert_with(|| {
+                suggested_types.push(interface);
+                0
+            });
+            *count += 1;
+        }
+    }
+
+    let is_interface = |name: &str| {
+        matches!(
+            schema.type_by_name(name),
+            Some(TypeDefinition::Interface(_))
+        )
+    };
+
+    suggested_types.sort_by(|a, b| {
+        usage_count[b]
+            .cmp(&usage_count[a])
+            .then_with(|| {
+                if is_interface(a) && schema.is_named_subtype(b, a) {
+                    std::cmp::Ordering::Less
+                } else if is_interface(b) && schema.is_named_subtype(a, b) {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .then_with(|| a.cmp(b))
+    });
+
+    suggested_types.into_iter().map(String::from).collect()
+}
+
+/// For an object or interface parent type, its fields with a name close to the unknown one.
+fn suggested_field_names(parent_type: &TypeDefinition, field_name: &str) -> Vec<String> {
+    match parent_type {
+        TypeDefinition::Object(object) => {
+            suggestion_list(field_name, object.fields.iter().map(|f| &f.name))
+        }
+        TypeDefinition::Interface(interface) => {
+            suggestion_list(field_name, interface.fields.iter().map(|f| &f.name))
+        }
+        _ => vec![],
+    }
+}
+
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
     fn enter_operation_definition(
         &mut self,
@@ -38,7 +118,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
                 if let Selection::Field(field) = selection {
                     if field.name == "__typename" {
                         user_context.report_error(ValidationError {
-                          error_code: self.error_code(),
+                          error_code: self.error_code().into(),
+                          details: ValidationErrorDetails { field_name: Some("__typename".to_string()), ..Default::default() },
                           message: "`__typename` may not be included as a root field in a subscription operation".to_string(),
                           locations: vec![subscription.position],
                         });
@@ -58,17 +139,40 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FieldsOnCorrectType {
             let field_name = &field.name;
             let type_name = parent_type.name();
 
-            if field.name.starts_with("__") {
-                return;
-            }
+            if visitor_context
+                .schema
+                .field_by_name(type_name, field_name)
+                .is_none()
+            {
+                // Suggest an inline fragment on a type defining the field first, then similar
+                // field names.
+                let suggested_types =
+                    suggested_type_names(visitor_context.schema, parent_type, field_name);
+                let (suggestion, suggestions) = if suggested_types.is_empty() {
+                    let suggested_fields = suggested_field_names(parent_type, field_name);
+                    (did_you_mean(&suggested_fields), suggested_fields)
+                } else {
+                    (
+                        did_you_mean_with_message(
+                            Some("to use an inline fragment on"),
+                            &suggested_types,
+                        ),
+                        suggested_types,
+                    )
+                };
 
-            if parent_type.field_by_name(field_name).is_none() {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        type_name: Some(type_name.to_string()),
+                        field_name: Some(field_name.to_string()),
+                        suggestions,
+                        ..Default::default()
+                    },
                     locations: vec![field.position],
                     message: format!(
-                        "Cannot query field \"{}\" on type \"{}\".",
-                        field_name, type_name
+                        "Cannot query field \"{}\" on type \"{}\".{}",
+                        field_name, type_name, suggestion
                     ),
                 });
             }
@@ -81,17 +185,12 @@ impl ValidationRule for FieldsOnCorrectType {
         "FieldsOnCorrectType"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut FieldsOnCorrectType::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(FieldsOnCorrectType::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -333,7 +432,7 @@ fn field_not_defined_on_fragment() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"meowVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"meowVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -402,7 +501,7 @@ fn field_not_defined_on_inline_fragment() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"meowVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"meowVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -423,7 +522,7 @@ fn aliased_field_target_not_defined() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"mooVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"mooVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -444,7 +543,7 @@ fn aliased_lying_field_target_not_defined() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"kawVolume\" on type \"Dog\"."]
+        vec!["Cannot query field \"kawVolume\" on type \"Dog\". Did you mean \"barkVolume\"?"]
     );
 }
 
@@ -486,7 +585,7 @@ fn defined_on_implementors_but_not_on_interface() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"nickname\" on type \"Pet\"."]
+        vec!["Cannot query field \"nickname\" on type \"Pet\". Did you mean to use an inline fragment on \"Cat\" or \"Dog\"?"]
     );
 }
 
@@ -528,7 +627,7 @@ fn defined_on_implementors_queried_on_union() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Cannot query field \"name\" on type \"CatOrDog\"."]
+        vec!["Cannot query field \"name\" on type \"CatOrDog\". Did you mean to use an inline fragment on \"Pet\", \"Cat\", or \"Dog\"?"]
     );
 }
 
@@ -591,3 +690,60 @@ fn forbidden_typename_on_subscription_type() {
         vec!["`__typename` may not be included as a root field in a subscription operation"]
     );
 }
+
+#[test]
+fn suggestions_are_available_on_the_error() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(FieldsOnCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "fragment nicknameOnPet on Pet {
+          nickname
+        }",
+        FIELDS_ON_CORRECT_TYPE_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(errors[0].details.suggestions, vec!["Cat", "Dog"]);
+}
+
+#[test]
+fn fields_of_introspection_types() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(FieldsOnCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "{
+          __typename
+          __type(name: \"Dog\") {
+            name
+            fields(includeDeprecated: true) {
+              name
+            }
+          }
+          __schema {
+            __typename
+            nonsense {
+              x
+            }
+          }
+          dog {
+            __schema {
+              queryType {
+                name
+              }
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Cannot query field \"nonsense\" on type \"__Schema\".",
+            "Cannot query field \"__schema\" on type \"Dog\".",
+        ]
+    );
+}
@@ -1,10 +1,9 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
-    TypeDefinitionExtension,
+    OperationVisitor, OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
 };
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Fragments on composite type
 ///
@@ -39,7 +38,11 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FragmentsOnCompositeTy
                 if !gql_type.is_composite_type() {
                     user_context.report_error(ValidationError {
                         locations: vec![inline_fragment.position],
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(type_condition.to_string()),
+                            ..Default::default()
+                        },
                         message: format!(
                             "Fragment cannot condition on non composite type \"{}\".",
                             type_condition
@@ -62,7 +65,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for FragmentsOnCompositeTy
             if !gql_type.is_composite_type() {
                 user_context.report_error(ValidationError {
                     locations: vec![fragment_definition.position],
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        type_name: Some(type_condition.to_string()),
+                        fragment_name: Some(fragment_definition.name.to_string()),
+                        ..Default::default()
+                    },
                     message: format!(
                         "Fragment \"{}\" cannot condition on non composite type \"{}\".",
                         fragment_definition.name, type_condition
@@ -78,17 +86,12 @@ impl ValidationRule for FragmentsOnCompositeTypes {
         "FragmentsOnCompositeTypes"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut FragmentsOnCompositeTypes::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(FragmentsOnCompositeTypes::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -1,12 +1,12 @@
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
-use crate::ast::{
-    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension,
-};
+use crate::ast::{OperationVisitor, OperationVisitorContext, SchemaDocumentExtension};
 use crate::static_graphql::query::Directive;
 use crate::static_graphql::schema::{InputValue, TypeDefinition};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
+
 /// Known argument names
 ///
 /// A GraphQL field/directive is only valid if all supplied arguments are defined by
@@ -69,7 +69,10 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownArgumentNames<'a>
         field: &crate::static_graphql::query::Field,
     ) {
         if let Some(parent_type) = visitor_context.current_parent_type() {
-            if let Some(field_def) = parent_type.field_by_name(&field.name) {
+            if let Some(field_def) = visitor_context
+                .schema
+                .field_by_name(parent_type.name(), &field.name)
+            {
                 self.current_known_arguments = Some((
                     ArgumentParent::Field(
                         &field_def.name,
@@ -94,33 +97,50 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownArgumentNames<'a>
 
     fn enter_argument(
         &mut self,
-        _: &mut OperationVisitorContext,
+        visitor_context: &mut OperationVisitorContext,
         user_context: &mut ValidationErrorContext,
         (argument_name, _argument_value): &(String, crate::static_graphql::query::Value),
     ) {
         if let Some((arg_position, args)) = &self.current_known_arguments {
             if !args.iter().any(|a| a.name.eq(argument_name)) {
+                let suggestions = suggestion_list(argument_name, args.iter().map(|a| &a.name));
+                let suggestion = did_you_mean(&suggestions);
+
                 match arg_position {
                     ArgumentParent::Field(field_name, type_name) => {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails {
+                                type_name: Some(type_name.name().to_string()),
+                                field_name: Some(field_name.to_string()),
+                                argument_name: Some(argument_name.to_string()),
+                                suggestions,
+                                ..Default::default()
+                            },
                             message: format!(
-                                "Unknown argument \"{}\" on field \"{}.{}\".",
+                                "Unknown argument \"{}\" on field \"{}.{}\".{}",
                                 argument_name,
                                 type_name.name(),
-                                field_name
+                                field_name,
+                                suggestion
                             ),
-                            locations: vec![],
+                            locations: visitor_context.current_position().into_iter().collect(),
                         })
                     }
                     ArgumentParent::Directive(directive_name) => {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails {
+                                directive_name: Some(directive_name.to_string()),
+                                argument_name: Some(argument_name.to_string()),
+                                suggestions,
+                                ..Default::default()
+                            },
                             message: format!(
-                                "Unknown argument \"{}\" on directive \"@{}\".",
-                                argument_name, directive_name
+                                "Unknown argument \"{}\" on directive \"@{}\".{}",
+                                argument_name, directive_name, suggestion
                             ),
-                            locations: vec![],
+                            locations: visitor_context.current_position().into_iter().collect(),
                         })
                     }
                 };
@@ -134,17 +154,12 @@ impl<'k> ValidationRule for KnownArgumentNames<'k> {
         "KnownArgumentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownArgumentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(KnownArgumentNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -329,7 +344,6 @@ fn arg_passed_to_directive_without_arg_is_reported() {
 }
 
 #[test]
-#[ignore = "Suggestions are not yet supported"]
 fn misspelled_directive_args_are_reported() {
     use crate::validation::test_utils::*;
 
@@ -346,7 +360,7 @@ fn misspelled_directive_args_are_reported() {
     assert_eq!(messages.len(), 1);
     assert_eq!(
         messages,
-        vec!["Unknown argument \"iff\" on directive \"@onField\". Did you mean \"if\"?"]
+        vec!["Unknown argument \"iff\" on directive \"@skip\". Did you mean \"if\"?"]
     );
 }
 
@@ -372,7 +386,6 @@ fn invalid_arg_name() {
 }
 
 #[test]
-#[ignore = "Suggestions are not yet supported"]
 fn misspelled_arg_name_is_reported() {
     use crate::validation::test_utils::*;
 
@@ -449,3 +462,74 @@ fn unknown_args_deeply() {
         ]
     );
 }
+
+#[test]
+fn reports_field_and_directive_locations() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownArgumentNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            doesKnowCommand(unknown: true)
+            name @skip(unless: true)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 2);
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 13
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 4,
+            column: 18
+        }]
+    );
+}
+
+#[test]
+fn reports_structured_details() {
+    use crate::validation::test_utils::*;
+    use crate::validation::utils::{ValidationErrorDetails, ValidationRuleCode};
+
+    let plan = create_plan_from_rule(Box::new(KnownArgumentNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            doesKnowCommand(unknown: true)
+            name @skip(unless: true)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(errors[0].error_code, ValidationRuleCode::KnownArgumentNames);
+    assert_eq!(
+        errors[0].details,
+        ValidationErrorDetails {
+            type_name: Some("Dog".to_string()),
+            field_name: Some("doesKnowCommand".to_string()),
+            argument_name: Some("unknown".to_string()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        errors[1].details,
+        ValidationErrorDetails {
+            directive_name: Some("skip".to_string()),
+            argument_name: Some("unless".to_string()),
+            ..Default::default()
+        }
+    );
+}
@@ -1,10 +1,12 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext, SchemaDocumentExtension};
 use crate::static_graphql::query::{
     Directive, Field, FragmentDefinition, InlineFragment, OperationDefinition,
 };
 use crate::static_graphql::schema::DirectiveLocation;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
 
 /// Known Directives
 ///
@@ -132,7 +134,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
         user_context: &mut ValidationErrorContext,
         directive: &Directive,
     ) {
-        if let Some(directive_type) = visitor_context.directives.get(&directive.name) {
+        if let Some(directive_type) = visitor_context.schema.directive_by_name(&directive.name) {
             if let Some(current_location) = &self.recent_location {
                 if !directive_type
                     .locations
@@ -140,7 +142,11 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
                     .any(|l| l == current_location)
                 {
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            directive_name: Some(directive.name.to_string()),
+                            ..Default::default()
+                        },
                         locations: vec![directive.position],
                         message: format!(
                             "Directive \"@{}\" may not be used on {}",
@@ -151,10 +157,27 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownDirectives {
                 }
             }
         } else {
+            let suggestions =
+                suggestion_list(&directive.name, visitor_context.schema.directive_names());
+
             user_context.report_error(ValidationError {
-                error_code: self.error_code(),
+                error_code: self.error_code().into(),
                 locations: vec![directive.position],
-                message: format!("Unknown directive \"@{}\".", directive.name),
+                message: format!(
+                    "Unknown directive \"@{}\".{}",
+                    directive.name,
+                    did_you_mean(
+                        &suggestions
+                            .iter()
+                            .map(|name| format!("@{}", name))
+                            .collect::<Vec<_>>()
+                    )
+                ),
+                details: ValidationErrorDetails {
+                    directive_name: Some(directive.name.to_string()),
+                    suggestions,
+                    ..Default::default()
+                },
             });
         }
     }
@@ -165,17 +188,12 @@ impl ValidationRule for KnownDirectives {
         "KnownDirectives"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownDirectives::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(KnownDirectives::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -330,3 +348,45 @@ fn misplaced_directives() {
 
     assert_eq!(get_messages(&errors).len(), 11);
 }
+
+#[test]
+fn misspelled_directives_are_suggested() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownDirectives::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name @inlcude(if: true)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Unknown directive \"@inlcude\". Did you mean \"@include\"?"]
+    );
+}
+
+#[test]
+fn one_of_is_a_known_directive() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownDirectives::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog @oneOf {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Directive \"@oneOf\" may not be used on FIELD"]
+    );
+}
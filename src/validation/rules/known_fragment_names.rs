@@ -1,7 +1,9 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
 
 /// Known fragment names
 ///
@@ -34,10 +36,24 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownFragmentNames {
             .known_fragments
             .contains_key(fragment_spread.fragment_name.as_str())
         {
+            let suggestions = suggestion_list(
+                &fragment_spread.fragment_name,
+                visitor_context.known_fragments.keys(),
+            );
+
             user_context.report_error(ValidationError {
-                error_code: self.error_code(),
+                error_code: self.error_code().into(),
                 locations: vec![fragment_spread.position],
-                message: format!("Unknown fragment \"{}\".", fragment_spread.fragment_name),
+                message: format!(
+                    "Unknown fragment \"{}\".{}",
+                    fragment_spread.fragment_name,
+                    did_you_mean(&suggestions)
+                ),
+                details: ValidationErrorDetails {
+                    fragment_name: Some(fragment_spread.fragment_name.to_string()),
+                    suggestions,
+                    ..Default::default()
+                },
             })
         }
     }
@@ -48,17 +64,12 @@ impl ValidationRule for KnownFragmentNames {
         "KnownFragmentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownFragmentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(KnownFragmentNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -129,3 +140,28 @@ fn invalid_fragment() {
         ]
     );
 }
+
+#[test]
+fn misspelled_fragment_names_are_suggested() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownFragmentNames::new()));
+    let errors = test_operation_with_schema(
+        "{
+          human(id: 4) {
+            ...HumanFeilds
+          }
+        }
+        fragment HumanFields on Human {
+          name
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Unknown fragment \"HumanFeilds\". Did you mean \"HumanFields\"?"]
+    );
+    assert_eq!(errors[0].details.suggestions, vec!["HumanFields"]);
+}
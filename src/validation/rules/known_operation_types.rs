@@ -0,0 +1,134 @@
+use super::ValidationRule;
+use crate::ast::{
+    OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+    SchemaDocumentExtension,
+};
+use crate::static_graphql::query::OperationDefinition;
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Known operation types
+///
+/// A GraphQL document is only valid if the schema has a root type for each of its operation
+/// types.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/KnownOperationTypesRule.ts
+pub struct KnownOperationTypes;
+
+impl Default for KnownOperationTypes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnownOperationTypes {
+    pub fn new() -> Self {
+        KnownOperationTypes
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownOperationTypes {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        if visitor_context
+            .schema
+            .root_operation_type(operation)
+            .is_some()
+        {
+            return;
+        }
+
+        let operation_types = match operation {
+            OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_) => "queries",
+            OperationDefinition::Mutation(_) => "mutations",
+            OperationDefinition::Subscription(_) => "subscriptions",
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code().into(),
+            locations: vec![operation.position()],
+            message: format!("Schema is not configured for {}.", operation_types),
+            details: ValidationErrorDetails::default(),
+        });
+    }
+}
+
+impl ValidationRule for KnownOperationTypes {
+    fn error_code<'a>(&self) -> &'a str {
+        "KnownOperationTypes"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(KnownOperationTypes::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[test]
+fn operation_types_of_the_schema() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownOperationTypes::new()));
+    let errors = test_operation_with_schema(
+        "query { dog { name } }
+        mutation { testInput }
+        subscription { newMessage }",
+        "type Query { dog: Dog }
+        type Dog { name: String }
+        type Mutation { testInput: String }
+        type Subscription { newMessage: String }",
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn unknown_operation_types() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownOperationTypes::new()));
+    let errors = test_operation_with_schema(
+        "query { a }
+        mutation { b }
+        subscription { c }",
+        "schema { query: QueryRoot }
+        type QueryRoot { a: Int }
+        type Mutation { b: Int }
+        type Subscription { c: Int }",
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Schema is not configured for mutations.",
+            "Schema is not configured for subscriptions.",
+        ]
+    );
+    assert_eq!(errors[0].locations, vec![Pos { line: 2, column: 9 }]);
+}
+
+#[test]
+fn schema_without_query_type() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(KnownOperationTypes::new()));
+    let errors = test_operation_with_schema(
+        "{ a }",
+        "schema { mutation: Mutation } type Mutation { a: Int }",
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Schema is not configured for queries."]
+    );
+}
@@ -1,10 +1,14 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
+    OperationVisitor, OperationVisitorContext, PreparedSchema, SchemaDocumentExtension,
     TypeExtension,
 };
+use crate::parser::Pos;
 use crate::static_graphql::query::TypeCondition;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{
+    did_you_mean, suggestion_list, ValidationError, ValidationErrorContext,
+    ValidationErrorDetails,
+};
 
 /// Known type names
 ///
@@ -24,6 +28,27 @@ impl KnownTypeNames {
     pub fn new() -> Self {
         KnownTypeNames
     }
+
+    fn unknown_type_error(
+        &self,
+        schema: &PreparedSchema,
+        type_name: &str,
+        position: Pos,
+        details: ValidationErrorDetails,
+    ) -> ValidationError {
+        let suggestions = suggestion_list(type_name, schema.type_map().into_keys());
+
+        ValidationError {
+            error_code: self.error_code().into(),
+            locations: vec![position],
+            message: format!("Unknown type \"{}\".{}", type_name, did_you_mean(&suggestions)),
+            details: ValidationErrorDetails {
+                type_name: Some(type_name.to_string()),
+                suggestions,
+                ..details
+            },
+        }
+    }
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
@@ -36,11 +61,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
         let TypeCondition::On(fragment_type_name) = &fragment_definition.type_condition;
 
         if visitor_context.schema.type_by_name(fragment_type_name).is_none() && !fragment_type_name.starts_with("__") {
-            user_context.report_error(ValidationError {
-                error_code: self.error_code(),
-                locations: vec![fragment_definition.position],
-                message: format!("Unknown type \"{}\".", fragment_type_name),
-            });
+            user_context.report_error(self.unknown_type_error(
+                visitor_context.schema,
+                fragment_type_name,
+                fragment_definition.position,
+                ValidationErrorDetails {
+                    fragment_name: Some(fragment_definition.name.to_string()),
+                    ..Default::default()
+                },
+            ));
         }
     }
 
@@ -52,11 +81,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
     ) {
         if let Some(TypeCondition::On(fragment_type_name)) = &inline_fragment.type_condition {
             if visitor_context.schema.type_by_name(fragment_type_name).is_none() && !fragment_type_name.starts_with("__") {
-                user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    locations: vec![inline_fragment.position],
-                    message: format!("Unknown type \"{}\".", fragment_type_name),
-                });
+                user_context.report_error(self.unknown_type_error(
+                    visitor_context.schema,
+                    fragment_type_name,
+                    inline_fragment.position,
+                    ValidationErrorDetails::default(),
+                ));
             }
         }
     }
@@ -70,11 +100,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownTypeNames {
         let base_type = variable_definition.var_type.inner_type();
 
         if visitor_context.schema.type_by_name(base_type).is_none() && !base_type.starts_with("__") {
-            user_context.report_error(ValidationError {
-                error_code: self.error_code(),
-                locations: vec![variable_definition.position],
-                message: format!("Unknown type \"{}\".", base_type),
-            });
+            user_context.report_error(self.unknown_type_error(
+                visitor_context.schema,
+                base_type,
+                variable_definition.position,
+                ValidationErrorDetails {
+                    variable_name: Some(variable_definition.name.to_string()),
+                    ..Default::default()
+                },
+            ));
         }
     }
 }
@@ -84,17 +118,12 @@ impl ValidationRule for KnownTypeNames {
         "KnownTypeNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut KnownTypeNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(KnownTypeNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -152,7 +181,7 @@ fn unknown_type_names_are_invalid() {
         vec![
             "Unknown type \"JumbledUpLetters\".",
             "Unknown type \"Badger\".",
-            "Unknown type \"Peat\"."
+            "Unknown type \"Peat\". Did you mean \"Pet\" or \"Cat\"?"
         ]
     );
 }
@@ -1,7 +1,7 @@
 use super::ValidationRule;
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext, TypeDefinitionExtension},
-    validation::utils::{ValidationError, ValidationErrorContext},
+    ast::{OperationVisitor, OperationVisitorContext, TypeDefinitionExtension},
+    validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails},
 };
 
 /// Leaf Field Selections
@@ -39,7 +39,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LeafFieldSelections {
             if field_type.is_leaf_type() {
                 if field_selection_count > 0 {
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(field_type.name().to_string()),
+                            field_name: Some(field.name.to_string()),
+                            ..Default::default()
+                        },
                         locations: vec![field.position],
                         message: format!(
                   "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
@@ -49,7 +54,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LeafFieldSelections {
                     });
                 }
             } else if field_selection_count == 0 {
-                      user_context.report_error(ValidationError {error_code: self.error_code(),
+                      user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(field_type.name().to_string()),
+                            field_name: Some(field.name.to_string()),
+                            ..Default::default()
+                        },
                 locations: vec![field.position],
                 message: format!(
                     "Field \"{}\" of type \"{}\" must have a selection of subfields. Did you mean \"{} {{ ... }}\"?",
@@ -68,17 +78,12 @@ impl ValidationRule for LeafFieldSelections {
         "LeafFieldSelections"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut LeafFieldSelections::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(LeafFieldSelections::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
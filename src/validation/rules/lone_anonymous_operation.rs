@@ -1,7 +1,7 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Lone Anonymous Operation
 ///
@@ -44,20 +44,22 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LoneAnonymousOperation
 
         for definition in &document.definitions {
             match definition {
-                Definition::Operation(OperationDefinition::SelectionSet(_)) => {
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
                     if operations_count > 1 {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails::default(),
                             message: "This anonymous operation must be the only defined operation."
                                 .to_string(),
-                            locations: vec![],
+                            locations: vec![selection_set.span.0],
                         })
                     }
                 }
                 Definition::Operation(OperationDefinition::Query(query)) => {
                     if query.name.is_none() && operations_count > 1 {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails::default(),
                             message: "This anonymous operation must be the only defined operation."
                                 .to_string(),
                             locations: vec![query.position],
@@ -67,7 +69,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LoneAnonymousOperation
                 Definition::Operation(OperationDefinition::Mutation(mutation)) => {
                     if mutation.name.is_none() && operations_count > 1 {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails::default(),
                             message: "This anonymous operation must be the only defined operation."
                                 .to_string(),
                             locations: vec![mutation.position],
@@ -77,7 +80,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for LoneAnonymousOperation
                 Definition::Operation(OperationDefinition::Subscription(subscription)) => {
                     if subscription.name.is_none() && operations_count > 1 {
                         user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails::default(),
                             message: "This anonymous operation must be the only defined operation."
                                 .to_string(),
                             locations: vec![subscription.position],
@@ -95,17 +99,12 @@ impl ValidationRule for LoneAnonymousOperation {
         "LoneAnonymousOperation"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut LoneAnonymousOperation::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(LoneAnonymousOperation::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -0,0 +1,184 @@
+use super::ValidationRule;
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::OperationDefinition;
+use crate::validation::utils::{
+    FieldCounter, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
+
+/// Max aliases
+///
+/// Limits the number of aliased fields in an operation, including the fields of the fragments
+/// it spreads, once per spread.
+#[derive(Clone, Debug)]
+pub struct MaxAliases {
+    max_aliases: usize,
+}
+
+impl MaxAliases {
+    pub fn new(max_aliases: usize) -> Self {
+        MaxAliases { max_aliases }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxAliases {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        let mut counter = FieldCounter::new(&visitor_context.known_fragments, true, |field| {
+            field.alias.is_some()
+        });
+        let count = counter.count(operation.selection_set());
+
+        if count > self.max_aliases {
+            let position = counter
+                .nth(operation.selection_set(), self.max_aliases)
+                .map_or_else(|| operation.position(), |field| field.position);
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![position],
+                message: format!(
+                    "Aliases limit of {} exceeded, found {}.",
+                    self.max_aliases, count
+                ),
+                details: ValidationErrorDetails {
+                    operation_name: operation.node_name().map(|name| name.to_string()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for MaxAliases {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxAliases"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxAliases::new(2)));
+    let errors = test_operation_with_schema(
+        "{
+          first: dog {
+            nickname: name
+          }
+          human {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn counts_aliases_through_fragments() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxAliases::new(2)));
+    let errors = test_operation_with_schema(
+        "query Aliases {
+          dog {
+            ...DogNames
+            ... on Dog {
+              ...DogNames
+            }
+          }
+        }
+        fragment DogNames on Dog {
+          a: name
+          b: nickname
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Aliases limit of 2 exceeded, found 4."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 10,
+            column: 11
+        }]
+    );
+    assert_eq!(
+        errors[0].details.operation_name,
+        Some("Aliases".to_string())
+    );
+}
+
+#[test]
+fn repeated_fragment_spreads_are_counted_without_walking_them_again() {
+    use crate::validation::test_utils::*;
+
+    // Each fragment spreads the next one twice, for 2^20 aliases in total.
+    let mut operation = String::from("{ dog { ...F0 } }");
+    for i in 0..20 {
+        operation.push_str(&format!(
+            " fragment F{} on Dog {{ ...F{} ...F{} }}",
+            i,
+            i + 1,
+            i + 1
+        ));
+    }
+    operation.push_str(" fragment F20 on Dog { alias: name }");
+
+    let plan = create_plan_from_rule(Box::new(MaxAliases::new(1000)));
+    let errors = test_operation_with_schema(&operation, TEST_SCHEMA, &plan);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Aliases limit of 1000 exceeded, found 1048576."]
+    );
+}
+
+#[test]
+fn exponential_fragment_spreads_saturate_the_count() {
+    use crate::validation::test_utils::*;
+
+    // 2^69 aliases, more than `usize` can count.
+    let mut operation = String::from("{ ...F0 }");
+    for i in 0..69 {
+        operation.push_str(&format!(
+            " fragment F{} on Query {{ ...F{} ...F{} }}",
+            i,
+            i + 1,
+            i + 1
+        ));
+    }
+    operation.push_str(" fragment F69 on Query { alias: dog { name } }");
+
+    let plan = create_plan_from_rule(Box::new(MaxAliases::new(10)));
+    let errors = test_operation_with_schema(&operation, TEST_SCHEMA, &plan);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![&format!(
+            "Aliases limit of 10 exceeded, found {}.",
+            usize::MAX
+        )]
+    );
+}
@@ -0,0 +1,199 @@
+use super::ValidationRule;
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::OperationDefinition;
+use crate::validation::cost::{analyze_operation_cost, CostOptions};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Max cost
+///
+/// Limits the estimated cost of an operation, computed by `analyze_operation_cost` from the
+/// `@cost` and `@listSize` directives of the schema.
+///
+/// Variables are not known during validation, so a list size given by a variable counts as
+/// `@listSize(assumedSize:)`, and exceeds any limit without it. A client can still select more
+/// items than `assumedSize` with a variable: servers should limit the slicing arguments too, or
+/// check the cost with the request variables using `analyze_operation_cost`.
+#[derive(Clone, Debug)]
+pub struct MaxCost {
+    max_cost: f64,
+    options: CostOptions,
+}
+
+impl MaxCost {
+    pub fn new(max_cost: f64) -> Self {
+        MaxCost {
+            max_cost,
+            options: CostOptions::default(),
+        }
+    }
+
+    /// The weights used for fields and lists without cost directives.
+    pub fn with_options(mut self, options: CostOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxCost {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        let analysis = analyze_operation_cost(
+            visitor_context.schema,
+            operation,
+            &visitor_context.known_fragments,
+            None,
+            &self.options,
+        );
+
+        if analysis.total > self.max_cost {
+            let message = if analysis.total.is_infinite() {
+                format!(
+                    "Query cost limit of {} exceeded, found a list sized by a variable without an assumed size.",
+                    self.max_cost
+                )
+            } else {
+                format!(
+                    "Query cost limit of {} exceeded, found {}.",
+                    self.max_cost, analysis.total
+                )
+            };
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![operation.position()],
+                message,
+                details: ValidationErrorDetails {
+                    operation_name: operation.node_name().map(|name| name.to_string()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for MaxCost {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxCost"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::cost::COST_TEST_SCHEMA;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxCost::new(10.0)));
+    let errors = test_operation_with_schema(
+        "{
+          users(first: 5) {
+            name
+          }
+        }",
+        COST_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn exceeding_the_limit() {
+    use crate::validation::cost::COST_TEST_SCHEMA;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxCost::new(10.0)));
+    let errors = test_operation_with_schema(
+        "query Users {
+          users(first: 5) {
+            friends(first: 10) {
+              name
+            }
+          }
+        }",
+        COST_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Query cost limit of 10 exceeded, found 55."]
+    );
+    assert_eq!(errors[0].details.operation_name, Some("Users".to_string()));
+}
+
+#[test]
+fn charges_variables_as_assumed_sizes_and_uses_options() {
+    use crate::validation::cost::{CostOptions, COST_TEST_SCHEMA};
+    use crate::validation::test_utils::*;
+
+    let operation = "query Users($first: Int = 20) {
+      users(first: $first) {
+        name
+      }
+      everyone {
+        name
+      }
+    }";
+
+    let plan = create_plan_from_rule(Box::new(MaxCost::new(20.0)));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(
+            operation,
+            COST_TEST_SCHEMA,
+            &plan
+        )),
+        vec!["Query cost limit of 20 exceeded, found 51."]
+    );
+
+    let plan = create_plan_from_rule(Box::new(MaxCost::new(20.0).with_options(CostOptions {
+        default_composite_weight: 0.0,
+        ..Default::default()
+    })));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(
+            operation,
+            COST_TEST_SCHEMA,
+            &plan
+        ))
+        .len(),
+        0
+    );
+}
+
+#[test]
+fn unbounded_list_sizes() {
+    use crate::validation::cost::COST_TEST_SCHEMA;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxCost::new(1000.0)));
+    let errors = test_operation_with_schema(
+        "query ($n: Int) {
+          user(id: 1) {
+            friends(first: $n) {
+              name
+            }
+          }
+        }",
+        COST_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Query cost limit of 1000 exceeded, found a list sized by a variable without an assumed size."]
+    );
+}
@@ -0,0 +1,321 @@
+use std::collections::{HashMap, HashSet};
+
+use super::ValidationRule;
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::parser::Pos;
+use crate::static_graphql::query::{
+    FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Max depth
+///
+/// Limits the nesting of field selections in an operation. Fragment spreads and inline
+/// fragments don't add to the depth of their fields, and fragment cycles are not followed
+/// (they are reported by `NoFragmentsCycle`).
+///
+/// `{ user { friends { name } } }` has a depth of 3.
+#[derive(Clone, Debug)]
+pub struct MaxDepth {
+    max_depth: usize,
+    ignore_introspection: bool,
+    ignore_typename: bool,
+}
+
+/// The deepest path found in a selection set, as the response keys and positions of its fields.
+type DepthPath<'a> = Vec<(&'a str, Pos)>;
+
+impl MaxDepth {
+    /// Introspection fields are ignored by default, `__typename` is counted.
+    pub fn new(max_depth: usize) -> Self {
+        MaxDepth {
+            max_depth,
+            ignore_introspection: true,
+            ignore_typename: false,
+        }
+    }
+
+    /// Whether the `__schema` and `__type` fields, and their selections, are left out.
+    pub fn ignore_introspection(mut self, ignore_introspection: bool) -> Self {
+        self.ignore_introspection = ignore_introspection;
+        self
+    }
+
+    /// Whether `__typename` fields are left out.
+    pub fn ignore_typename(mut self, ignore_typename: bool) -> Self {
+        self.ignore_typename = ignore_typename;
+        self
+    }
+
+    fn is_ignored(&self, field_name: &str) -> bool {
+        match field_name {
+            "__typename" => self.ignore_typename,
+            "__schema" | "__type" => self.ignore_introspection,
+            _ => false,
+        }
+    }
+
+    fn deepest_path<'a>(
+        &self,
+        selection_set: &'a SelectionSet,
+        known_fragments: &HashMap<&'a str, &'a FragmentDefinition>,
+        visiting_fragments: &mut HashSet<&'a str>,
+        fragment_paths: &mut HashMap<&'a str, DepthPath<'a>>,
+    ) -> DepthPath<'a> {
+        let mut deepest: DepthPath<'a> = vec![];
+
+        for selection in &selection_set.items {
+            let path = match selection {
+                Selection::Field(field) => {
+                    if self.is_ignored(&field.name) {
+                        continue;
+                    }
+
+                    let response_key = field.alias.as_ref().unwrap_or(&field.name);
+                    let mut path = vec![(response_key.as_str(), field.position)];
+                    path.extend(self.deepest_path(
+                        &field.selection_set,
+                        known_fragments,
+                        visiting_fragments,
+                        fragment_paths,
+                    ));
+                    path
+                }
+                Selection::InlineFragment(inline_fragment) => self.deepest_path(
+                    &inline_fragment.selection_set,
+                    known_fragments,
+                    visiting_fragments,
+                    fragment_paths,
+                ),
+                Selection::FragmentSpread(fragment_spread) => {
+                    let fragment_name = fragment_spread.fragment_name.as_str();
+
+                    if let Some(path) = fragment_paths.get(fragment_name) {
+                        path.clone()
+                    } else if let Some(fragment) = known_fragments.get(fragment_name) {
+                        if !visiting_fragments.insert(fragment_name) {
+                            continue;
+                        }
+
+                        let path = self.deepest_path(
+                            &fragment.selection_set,
+                            known_fragments,
+                            visiting_fragments,
+                            fragment_paths,
+                        );
+                        visiting_fragments.remove(fragment_name);
+                        fragment_paths.insert(fragment_name, path.clone());
+                        path
+                    } else {
+                        continue;
+                    }
+                }
+            };
+
+            if path.len() > deepest.len() {
+                deepest = path;
+            }
+        }
+
+        deepest
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxDepth {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        let path = self.deepest_path(
+            operation.selection_set(),
+            &visitor_context.known_fragments,
+            &mut HashSet::new(),
+            &mut HashMap::new(),
+        );
+
+        if path.len() > self.max_depth {
+            let printed_path = path
+                .iter()
+                .map(|(response_key, _)| *response_key)
+                .collect::<Vec<_>>();
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![path[self.max_depth].1],
+                message: format!(
+                    "Query depth limit of {} exceeded, found {} at \"{}\".",
+                    self.max_depth,
+                    path.len(),
+                    printed_path.join(".")
+                ),
+                details: ValidationErrorDetails {
+                    operation_name: operation.node_name().map(|name| name.to_string()),
+                    path: printed_path.into_iter().map(String::from).collect(),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for MaxDepth {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxDepth"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(3)));
+    let errors = test_operation_with_schema(
+        "{
+          human {
+            pets {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn exceeding_the_limit_reports_the_path() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(2)));
+    let errors = test_operation_with_schema(
+        "query Deep {
+          human {
+            name
+            relatives: pets {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Query depth limit of 2 exceeded, found 3 at \"human.relatives.name\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 5,
+            column: 15
+        }]
+    );
+    assert_eq!(errors[0].details.operation_name, Some("Deep".to_string()));
+    assert_eq!(errors[0].details.path, vec!["human", "relatives", "name"]);
+}
+
+#[test]
+fn follows_fragments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(2)));
+    let errors = test_operation_with_schema(
+        "{
+          human {
+            ...HumanPets
+          }
+        }
+        fragment HumanPets on Human {
+          ... on Human {
+            pets {
+              ...PetName
+            }
+          }
+        }
+        fragment PetName on Pet {
+          name
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Query depth limit of 2 exceeded, found 3 at \"human.pets.name\"."]
+    );
+}
+
+#[test]
+fn fragment_cycles_are_not_followed() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(3)));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            ...DogFields
+          }
+        }
+        fragment DogFields on Dog {
+          name
+          ...DogFields
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn introspection_and_typename_options() {
+    use crate::validation::test_utils::*;
+
+    let operation = "{
+      __schema {
+        types {
+          name
+        }
+      }
+      dog {
+        __typename
+      }
+    }";
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(1)));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(operation, TEST_SCHEMA, &plan)),
+        vec!["Query depth limit of 1 exceeded, found 2 at \"dog.__typename\"."]
+    );
+
+    let plan = create_plan_from_rule(Box::new(MaxDepth::new(1).ignore_typename(true)));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(operation, TEST_SCHEMA, &plan)).len(),
+        0
+    );
+
+    let plan = create_plan_from_rule(Box::new(
+        MaxDepth::new(1)
+            .ignore_typename(true)
+            .ignore_introspection(false),
+    ));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(operation, TEST_SCHEMA, &plan)),
+        vec!["Query depth limit of 1 exceeded, found 3 at \"__schema.types.name\"."]
+    );
+}
@@ -0,0 +1,166 @@
+use super::ValidationRule;
+use crate::ast::{OperationDefinitionExtension, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::{
+    Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
+};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Max directives
+///
+/// Limits the number of directives at a single location: an operation, a fragment definition,
+/// a field, a fragment spread or an inline fragment. The fields of a fragment are checked once,
+/// however many times it is spread.
+#[derive(Clone, Debug)]
+pub struct MaxDirectives {
+    max_directives: usize,
+}
+
+impl MaxDirectives {
+    pub fn new(max_directives: usize) -> Self {
+        MaxDirectives { max_directives }
+    }
+
+    fn check_directives_count(
+        &self,
+        err_context: &mut ValidationErrorContext,
+        directives: &[Directive],
+    ) {
+        if directives.len() > self.max_directives {
+            let directive = &directives[self.max_directives];
+
+            err_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![directive.position],
+                message: format!(
+                    "Directives limit of {} exceeded, found {}.",
+                    self.max_directives,
+                    directives.len()
+                ),
+                details: ValidationErrorDetails {
+                    directive_name: Some(directive.name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxDirectives {
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        err_ctx: &mut ValidationErrorContext,
+        operation: &OperationDefinition,
+    ) {
+        self.check_directives_count(err_ctx, operation.directives());
+    }
+
+    fn enter_field(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        err_ctx: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        self.check_directives_count(err_ctx, &field.directives);
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        err_ctx: &mut ValidationErrorContext,
+        fragment: &FragmentDefinition,
+    ) {
+        self.check_directives_count(err_ctx, &fragment.directives);
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        err_ctx: &mut ValidationErrorContext,
+        fragment_spread: &FragmentSpread,
+    ) {
+        self.check_directives_count(err_ctx, &fragment_spread.directives)
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        err_ctx: &mut ValidationErrorContext,
+        inline_fragment: &InlineFragment,
+    ) {
+        self.check_directives_count(err_ctx, &inline_fragment.directives)
+    }
+}
+
+impl ValidationRule for MaxDirectives {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxDirectives"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDirectives::new(2)));
+    let errors = test_operation_with_schema(
+        "query Q @onQuery {
+          dog @onField @repeatable {
+            name @repeatable @repeatable
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn reports_each_location_above_the_limit() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxDirectives::new(2)));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name @repeatable @repeatable @repeatable
+            ...DogFields @onFragmentSpread @onFragmentSpread @onFragmentSpread @onFragmentSpread
+          }
+        }
+        fragment DogFields on Dog @repeatable @repeatable @onFragmentDefinition {
+          nickname
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Directives limit of 2 exceeded, found 3.",
+            "Directives limit of 2 exceeded, found 4.",
+            "Directives limit of 2 exceeded, found 3.",
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 42
+        }]
+    );
+    assert_eq!(
+        errors[2].details.directive_name,
+        Some("onFragmentDefinition".to_string())
+    );
+}
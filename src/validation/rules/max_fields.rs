@@ -0,0 +1,129 @@
+use super::ValidationRule;
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::OperationDefinition;
+use crate::validation::utils::{
+    FieldCounter, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
+
+/// Max fields
+///
+/// Limits the total number of fields in an operation, at any depth, including the fields of the
+/// fragments it spreads, once per spread.
+#[derive(Clone, Debug)]
+pub struct MaxFields {
+    max_fields: usize,
+}
+
+impl MaxFields {
+    pub fn new(max_fields: usize) -> Self {
+        MaxFields { max_fields }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxFields {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        let mut counter = FieldCounter::new(&visitor_context.known_fragments, true, |_| true);
+        let count = counter.count(operation.selection_set());
+
+        if count > self.max_fields {
+            let position = counter
+                .nth(operation.selection_set(), self.max_fields)
+                .map_or_else(|| operation.position(), |field| field.position);
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![position],
+                message: format!(
+                    "Fields limit of {} exceeded, found {}.",
+                    self.max_fields, count
+                ),
+                details: ValidationErrorDetails {
+                    operation_name: operation.node_name().map(|name| name.to_string()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for MaxFields {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxFields"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxFields::new(5)));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name
+            __typename
+          }
+          human {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn counts_fields_at_any_depth_through_fragments() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxFields::new(4)));
+    let errors = test_operation_with_schema(
+        "query Fields {
+          dog {
+            ...DogFields
+          }
+          pet {
+            ...DogFields
+          }
+        }
+        fragment DogFields on Dog {
+          name
+          mother {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Fields limit of 4 exceeded, found 8."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 5,
+            column: 11
+        }]
+    );
+    assert_eq!(errors[0].details.operation_name, Some("Fields".to_string()));
+}
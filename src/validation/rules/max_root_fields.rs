@@ -0,0 +1,132 @@
+use super::ValidationRule;
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::OperationDefinition;
+use crate::validation::utils::{
+    FieldCounter, ValidationError, ValidationErrorContext, ValidationErrorDetails,
+};
+
+/// Max root fields
+///
+/// Limits the number of fields selected on the root type of an operation, including the fields
+/// of the fragments it spreads at the root, once per spread.
+#[derive(Clone, Debug)]
+pub struct MaxRootFields {
+    max_root_fields: usize,
+}
+
+impl MaxRootFields {
+    pub fn new(max_root_fields: usize) -> Self {
+        MaxRootFields { max_root_fields }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MaxRootFields {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        operation: &'a OperationDefinition,
+    ) {
+        let mut counter = FieldCounter::new(&visitor_context.known_fragments, false, |_| true);
+        let count = counter.count(operation.selection_set());
+
+        if count > self.max_root_fields {
+            let position = counter
+                .nth(operation.selection_set(), self.max_root_fields)
+                .map_or_else(|| operation.position(), |field| field.position);
+
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![position],
+                message: format!(
+                    "Root fields limit of {} exceeded, found {}.",
+                    self.max_root_fields, count
+                ),
+                details: ValidationErrorDetails {
+                    operation_name: operation.node_name().map(|name| name.to_string()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for MaxRootFields {
+    fn error_code<'a>(&self) -> &'a str {
+        "MaxRootFields"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn within_the_limit() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxRootFields::new(2)));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name
+            nickname
+            barks
+          }
+          human {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn counts_root_fields_through_fragments() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(MaxRootFields::new(2)));
+    let errors = test_operation_with_schema(
+        "query Roots {
+          dog {
+            name
+          }
+          ...RootFields
+        }
+        fragment RootFields on QueryRoot {
+          cat {
+            name
+          }
+          ... on QueryRoot {
+            human {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Root fields limit of 2 exceeded, found 3."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 12,
+            column: 13
+        }]
+    );
+    assert_eq!(errors[0].details.operation_name, Some("Roots".to_string()));
+}
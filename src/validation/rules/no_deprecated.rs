@@ -0,0 +1,399 @@
+use super::ValidationRule;
+use crate::ast::{
+    OperationVisitor, OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
+};
+use crate::static_graphql::query::{Directive, Field, Value};
+use crate::static_graphql::schema::{self, TypeDefinition};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// No deprecated
+///
+/// Reports the use of deprecated fields, arguments, input fields and enum values, including in
+/// the default values of variables.
+///
+/// This is not part of the specification, it's meant to find the operations to migrate before
+/// removing deprecated parts of a schema.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/custom/NoDeprecatedCustomRule.ts
+pub struct NoDeprecated {
+    current_directive: Option<String>,
+    input_object_stack: Vec<Option<String>>,
+}
+
+impl Default for NoDeprecated {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoDeprecated {
+    pub fn new() -> Self {
+        NoDeprecated {
+            current_directive: None,
+            input_object_stack: Vec::new(),
+        }
+    }
+}
+
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// The reason given to `@deprecated`, when the directive is present.
+fn deprecation_reason(directives: &[schema::Directive]) -> Option<String> {
+    let deprecated = directives
+        .iter()
+        .find(|directive| directive.name == "deprecated")?;
+
+    let reason = deprecated
+        .arguments
+        .iter()
+        .find_map(|(name, value)| match value {
+            Value::String(reason) if name == "reason" => Some(reason.clone()),
+            _ => None,
+        });
+
+    Some(reason.unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string()))
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoDeprecated {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        let Some(parent_type) = visitor_context.current_parent_type() else {
+            return;
+        };
+        let Some(field_def) = visitor_context
+            .schema
+            .field_by_name(parent_type.name(), &field.name)
+        else {
+            return;
+        };
+
+        if let Some(reason) = deprecation_reason(&field_def.directives) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![field.position],
+                message: format!(
+                    "The field {}.{} is deprecated. {}",
+                    parent_type.name(),
+                    field_def.name,
+                    reason
+                ),
+                details: ValidationErrorDetails {
+                    type_name: Some(parent_type.name().to_string()),
+                    field_name: Some(field_def.name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        self.current_directive = Some(directive.name.clone());
+    }
+
+    fn leave_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &Directive,
+    ) {
+        self.current_directive = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        (argument_name, _): &'a (String, Value),
+    ) {
+        let error = match &self.current_directive {
+            Some(directive_name) => visitor_context
+                .schema
+                .directive_by_name(directive_name)
+                .and_then(|directive_def| {
+                    directive_def
+                        .arguments
+                        .iter()
+                        .find(|arg| &arg.name == argument_name)
+                })
+                .and_then(|argument_def| deprecation_reason(&argument_def.directives))
+                .map(|reason| {
+                    (
+                        format!(
+                            "Directive \"@{}\" argument \"{}\" is deprecated. {}",
+                            directive_name, argument_name, reason
+                        ),
+                        ValidationErrorDetails {
+                            directive_name: Some(directive_name.clone()),
+                            argument_name: Some(argument_name.clone()),
+                            ..Default::default()
+                        },
+                    )
+                }),
+            None => visitor_context
+                .current_parent_type()
+                .zip(visitor_context.current_field())
+                .and_then(|(parent_type, field_def)| {
+                    let argument_def = field_def
+                        .arguments
+                        .iter()
+                        .find(|arg| &arg.name == argument_name)?;
+                    let reason = deprecation_reason(&argument_def.directives)?;
+
+                    Some((
+                        format!(
+                            "Field \"{}.{}\" argument \"{}\" is deprecated. {}",
+                            parent_type.name(),
+                            field_def.name,
+                            argument_name,
+                            reason
+                        ),
+                        ValidationErrorDetails {
+                            type_name: Some(parent_type.name().to_string()),
+                            field_name: Some(field_def.name.clone()),
+                            argument_name: Some(argument_name.clone()),
+                            ..Default::default()
+                        },
+                    ))
+                }),
+        };
+
+        if let Some((message, details)) = error {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: visitor_context.current_position().into_iter().collect(),
+                message,
+                details,
+            });
+        }
+    }
+
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &std::collections::BTreeMap<String, Value>,
+    ) {
+        self.input_object_stack.push(
+            visitor_context
+                .current_input_type()
+                .map(|input_type| input_type.name().to_string()),
+        );
+    }
+
+    fn leave_object_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &std::collections::BTreeMap<String, Value>,
+    ) {
+        self.input_object_stack.pop();
+    }
+
+    fn enter_object_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        (field_name, _): &(String, Value),
+    ) {
+        let Some(Some(input_object_name)) = self.input_object_stack.last() else {
+            return;
+        };
+
+        let reason = visitor_context
+            .schema
+            .input_field_by_name(input_object_name, field_name)
+            .and_then(|input_field| deprecation_reason(&input_field.directives));
+
+        if let Some(reason) = reason {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: visitor_context.current_position().into_iter().collect(),
+                message: format!(
+                    "The input field {}.{} is deprecated. {}",
+                    input_object_name, field_name, reason
+                ),
+                details: ValidationErrorDetails {
+                    type_name: Some(input_object_name.clone()),
+                    field_name: Some(field_name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        value: &String,
+    ) {
+        let Some(TypeDefinition::Enum(enum_type)) = visitor_context.current_input_type() else {
+            return;
+        };
+
+        let reason = enum_type
+            .values
+            .iter()
+            .find(|enum_value| &enum_value.name == value)
+            .and_then(|enum_value| deprecation_reason(&enum_value.directives));
+
+        if let Some(reason) = reason {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: visitor_context.current_position().into_iter().collect(),
+                message: format!(
+                    "The enum value \"{}.{}\" is deprecated. {}",
+                    enum_type.name, value, reason
+                ),
+                details: ValidationErrorDetails {
+                    type_name: Some(enum_type.name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for NoDeprecated {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoDeprecated"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(NoDeprecated::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[cfg(test)]
+static DEPRECATIONS_TEST_SCHEMA: &str = r#"
+directive @cached(ttl: Int, maxAge: Int @deprecated(reason: "Use `ttl`.")) on FIELD
+
+enum Color {
+  RED
+  GREEN @deprecated
+  BLUE @deprecated(reason: "Use `RED`.")
+}
+
+input PaintInput {
+  color: Color
+  shade: Int @deprecated(reason: "Use `color`.")
+  nested: PaintInput
+}
+
+type Query {
+  color: Color
+  oldColor: Color @deprecated(reason: "Use `color`.")
+  legacy: String @deprecated
+  paint(input: PaintInput, color: Color, opacity: Int @deprecated(reason: "Always opaque.")): String
+}
+"#;
+
+#[test]
+fn no_deprecated_usage() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "query ($input: PaintInput = { color: RED }) {
+          color @cached(ttl: 10)
+          paint(input: $input, color: RED)
+        }",
+        DEPRECATIONS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn deprecated_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "{
+          oldColor
+          ...Legacy
+        }
+        fragment Legacy on Query {
+          legacy
+        }",
+        DEPRECATIONS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "The field Query.oldColor is deprecated. Use `color`.",
+            "The field Query.legacy is deprecated. No longer supported",
+        ]
+    );
+    assert_eq!(errors[0].details.type_name, Some("Query".to_string()));
+    assert_eq!(errors[0].details.field_name, Some("oldColor".to_string()));
+}
+
+#[test]
+fn deprecated_arguments() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "{
+          paint(opacity: 1) @cached(maxAge: 10)
+        }",
+        DEPRECATIONS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Field \"Query.paint\" argument \"opacity\" is deprecated. Always opaque.",
+            "Directive \"@cached\" argument \"maxAge\" is deprecated. Use `ttl`.",
+        ]
+    );
+    assert_eq!(errors[1].details.directive_name, Some("cached".to_string()));
+    assert_eq!(errors[1].details.argument_name, Some("maxAge".to_string()));
+}
+
+#[test]
+fn deprecated_input_fields_and_enum_values() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "query ($input: PaintInput = { nested: { shade: 1 } }, $color: Color = GREEN) {
+          paint(input: { color: BLUE, nested: { nested: { shade: 2 } } }, color: $color)
+        }",
+        DEPRECATIONS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "The input field PaintInput.shade is deprecated. Use `color`.",
+            "The enum value \"Color.GREEN\" is deprecated. No longer supported",
+            "The enum value \"Color.BLUE\" is deprecated. Use `RED`.",
+            "The input field PaintInput.shade is deprecated. Use `color`.",
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![crate::parser::Pos { line: 1, column: 8 }]
+    );
+}
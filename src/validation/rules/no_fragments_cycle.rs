@@ -1,8 +1,8 @@
 use super::ValidationRule;
 use crate::ast::ext::{AstNodeWithName, FragmentSpreadExtraction};
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::{FragmentDefinition, FragmentSpread};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 use std::collections::{HashMap, HashSet};
 
 /// No fragment cycles
@@ -80,7 +80,11 @@ impl NoFragmentsCycle {
                     };
 
                     error_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            fragment_name: Some(spread_name.to_string()),
+                            ..Default::default()
+                        },
                         locations: cycle_path.iter().map(|f| f.position).collect(),
                         message: match via_path.len() {
                             0 => {
@@ -128,17 +132,12 @@ impl ValidationRule for NoFragmentsCycle {
         "NoFragmentsCycle"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoFragmentsCycle::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(NoFragmentsCycle::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
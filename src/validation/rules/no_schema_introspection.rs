@@ -0,0 +1,177 @@
+use std::collections::BTreeSet;
+
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::Field;
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// No schema introspection
+///
+/// Rejects the introspection meta-fields (`__schema`, `__type` and any other field starting
+/// with `__`) wherever they are selected, in operations and fragments. `__typename` is allowed
+/// by default.
+///
+/// Operations don't say which client sent them, so to allow introspection to some clients only,
+/// validate their operations with a plan without this rule.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/custom/NoSchemaIntrospectionCustomRule.ts
+#[derive(Clone, Debug)]
+pub struct NoSchemaIntrospection {
+    allowed_fields: BTreeSet<String>,
+}
+
+impl Default for NoSchemaIntrospection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoSchemaIntrospection {
+    pub fn new() -> Self {
+        NoSchemaIntrospection {
+            allowed_fields: BTreeSet::from(["__typename".to_string()]),
+        }
+    }
+
+    /// Allows a meta-field, such as `__type`.
+    pub fn allow_field(mut self, field_name: &str) -> Self {
+        self.allowed_fields.insert(field_name.to_string());
+        self
+    }
+
+    /// Rejects `__typename` too.
+    pub fn disallow_typename(mut self) -> Self {
+        self.allowed_fields.remove("__typename");
+        self
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoSchemaIntrospection {
+    fn enter_field(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        if field.name.starts_with("__") && !self.allowed_fields.contains(&field.name) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![field.position],
+                message: format!(
+                    "GraphQL introspection has been disabled, but the requested query contained the field \"{}\".",
+                    field.name
+                ),
+                details: ValidationErrorDetails {
+                    field_name: Some(field.name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for NoSchemaIntrospection {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoSchemaIntrospection"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[test]
+fn allows_regular_fields_and_typename() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoSchemaIntrospection::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            __typename
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_introspection_in_fragments() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoSchemaIntrospection::new()));
+    let errors = test_operation_with_schema(
+        "query Introspection {
+          ...SchemaTypes
+          ... on QueryRoot {
+            __type(name: \"Dog\") {
+              name
+            }
+          }
+        }
+        fragment SchemaTypes on QueryRoot {
+          __schema {
+            types {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "GraphQL introspection has been disabled, but the requested query contained the field \"__type\".",
+            "GraphQL introspection has been disabled, but the requested query contained the field \"__schema\".",
+        ]
+    );
+    assert_eq!(errors[0].error_code, "NoSchemaIntrospection");
+    assert_eq!(errors[0].details.field_name, Some("__type".to_string()));
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 10,
+            column: 11
+        }]
+    );
+}
+
+#[test]
+fn allowlist() {
+    use crate::validation::test_utils::*;
+
+    let operation = "{
+      __type(name: \"Dog\") {
+        name
+      }
+      dog {
+        __typename
+      }
+    }";
+
+    let plan = create_plan_from_rule(Box::new(NoSchemaIntrospection::new().allow_field("__type")));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(operation, TEST_SCHEMA, &plan)).len(),
+        0
+    );
+
+    let plan = create_plan_from_rule(Box::new(NoSchemaIntrospection::new().disallow_typename()));
+    assert_eq!(
+        get_messages(&test_operation_with_schema(operation, TEST_SCHEMA, &plan)),
+        vec![
+            "GraphQL introspection has been disabled, but the requested query contained the field \"__type\".",
+            "GraphQL introspection has been disabled, but the requested query contained the field \"__typename\".",
+        ]
+    );
+}
@@ -1,9 +1,11 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext, ValueExtension,
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+    ValueExtension,
 };
+use crate::parser::Pos;
 use crate::static_graphql::query::{self, OperationDefinition};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 use std::collections::{HashMap, HashSet};
 
 /// No undefined variables
@@ -14,8 +16,9 @@ use std::collections::{HashMap, HashSet};
 /// See https://spec.graphql.org/draft/#sec-All-Variable-Uses-Defined
 pub struct NoUndefinedVariables<'a> {
     current_scope: Option<NoUndefinedVariablesScope<'a>>,
+    operations: Vec<(Option<&'a str>, Pos)>,
     defined_variables: HashMap<Option<&'a str>, HashSet<&'a str>>,
-    used_variables: HashMap<NoUndefinedVariablesScope<'a>, Vec<&'a str>>,
+    used_variables: HashMap<NoUndefinedVariablesScope<'a>, Vec<(&'a str, Pos)>>,
     spreads: HashMap<NoUndefinedVariablesScope<'a>, Vec<&'a str>>,
 }
 
@@ -29,6 +32,7 @@ impl<'a> NoUndefinedVariables<'a> {
     pub fn new() -> Self {
         Self {
             current_scope: None,
+            operations: Vec::new(),
             defined_variables: HashMap::new(),
             used_variables: HashMap::new(),
             spreads: HashMap::new(),
@@ -41,7 +45,7 @@ impl<'a> NoUndefinedVariables<'a> {
         &self,
         from: &NoUndefinedVariablesScope<'a>,
         defined: &HashSet<&str>,
-        unused: &mut HashSet<&'a str>,
+        undefined: &mut Vec<(&'a str, Pos)>,
         visited: &mut HashSet<NoUndefinedVariablesScope<'a>>,
     ) {
         if visited.contains(from) {
@@ -51,9 +55,9 @@ impl<'a> NoUndefinedVariables<'a> {
         visited.insert(from.clone());
 
         if let Some(used_vars) = self.used_variables.get(from) {
-            for var in used_vars {
-                if !defined.contains(*var) {
-                    unused.insert(*var);
+            for (var, position) in used_vars {
+                if !defined.contains(*var) && !undefined.iter().any(|(name, _)| name == var) {
+                    undefined.push((*var, *position));
                 }
             }
         }
@@ -63,7 +67,7 @@ impl<'a> NoUndefinedVariables<'a> {
                 self.find_undefined_vars(
                     &NoUndefinedVariablesScope::Fragment(spread),
                     defined,
-                    unused,
+                    undefined,
                     visited,
                 );
             }
@@ -85,6 +89,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
         operation_definition: &'a OperationDefinition,
     ) {
         let op_name = operation_definition.node_name();
+        self.operations
+            .push((op_name, operation_definition.position()));
         self.current_scope = Some(NoUndefinedVariablesScope::Operation(op_name));
         self.defined_variables.insert(op_name, HashSet::new());
     }
@@ -129,15 +135,22 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
 
     fn enter_argument(
         &mut self,
-        _: &mut OperationVisitorContext,
+        visitor_context: &mut OperationVisitorContext,
         _: &mut ValidationErrorContext,
         (_arg_name, arg_value): &'a (String, query::Value),
     ) {
-        if let Some(ref scope) = self.current_scope {
+        if let (Some(scope), Some(position)) =
+            (&self.current_scope, visitor_context.current_position())
+        {
             self.used_variables
                 .entry(scope.clone())
                 .or_default()
-                .append(&mut arg_value.variables_in_use());
+                .extend(
+                    arg_value
+                        .variables_in_use()
+                        .into_iter()
+                        .map(|var| (var, position)),
+                );
         }
     }
 
@@ -147,22 +160,29 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUndefinedVariables<'
         user_context: &mut ValidationErrorContext,
         _: &query::Document,
     ) {
-        for (op_name, def_vars) in &self.defined_variables {
-            let mut unused = HashSet::new();
+        for (op_name, op_position) in &self.operations {
+            let mut undefined = Vec::new();
             let mut visited = HashSet::new();
 
-            self.find_undefined_vars(
-                &NoUndefinedVariablesScope::Operation(*op_name),
-                def_vars,
-                &mut unused,
-                &mut visited,
-            );
+            if let Some(def_vars) = self.defined_variables.get(op_name) {
+                self.find_undefined_vars(
+                    &NoUndefinedVariablesScope::Operation(*op_name),
+                    def_vars,
+                    &mut undefined,
+                    &mut visited,
+                );
+            }
 
-            unused.iter().for_each(|var| {
+            undefined.iter().for_each(|(var, position)| {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        variable_name: Some(var.to_string()),
+                        operation_name: op_name.map(|name| name.to_string()),
+                        ..Default::default()
+                    },
                     message: error_message(var, op_name),
-                    locations: vec![],
+                    locations: vec![*position, *op_position],
                 })
             })
         }
@@ -185,17 +205,12 @@ impl<'n> ValidationRule for NoUndefinedVariables<'n> {
         "NoUndefinedVariables"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUndefinedVariables::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(NoUndefinedVariables::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -616,3 +631,47 @@ fn multiple_undefined_variables_produce_multiple_errors() {
     assert!(messages.contains(&&"Variable \"$b\" is not defined by operation \"Bar\".".to_owned()));
     assert!(messages.contains(&&"Variable \"$c\" is not defined by operation \"Bar\".".to_owned()));
 }
+
+#[test]
+fn reports_usage_and_operation_locations() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoUndefinedVariables::new()));
+    let errors = test_operation_with_schema(
+        "query Foo($a: String) {
+          field(a: $a) {
+            field(b: $b) @include(if: $c)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Variable \"$b\" is not defined by operation \"Foo\".",
+            "Variable \"$c\" is not defined by operation \"Foo\"."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 3,
+                column: 13
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![
+            Pos {
+                line: 3,
+                column: 26
+            },
+            Pos { line: 1, column: 1 }
+        ]
+    );
+}
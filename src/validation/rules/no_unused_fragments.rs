@@ -1,7 +1,7 @@
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// No unused fragments
 ///
@@ -30,20 +30,23 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedFragments<'a>
         user_context: &mut ValidationErrorContext,
         _document: &Document,
     ) {
-        visitor_context
+        let mut unused_fragments = visitor_context
             .known_fragments
             .iter()
-            .filter_map(|(fragment_name, _fragment)| {
-                if !self.fragments_in_use.contains(fragment_name) {
-                    Some(fragment_name)
-                } else {
-                    None
-                }
-            })
-            .for_each(|unused_fragment_name| {
+            .filter(|(fragment_name, _fragment)| !self.fragments_in_use.contains(fragment_name))
+            .collect::<Vec<_>>();
+        unused_fragments.sort_by_key(|(_fragment_name, fragment)| fragment.position);
+
+        unused_fragments
+            .into_iter()
+            .for_each(|(unused_fragment_name, fragment)| {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    locations: vec![],
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        fragment_name: Some(unused_fragment_name.to_string()),
+                        ..Default::default()
+                    },
+                    locations: vec![fragment.position],
                     message: format!("Fragment \"{}\" is never used.", unused_fragment_name),
                 });
             });
@@ -69,17 +72,12 @@ impl<'n> ValidationRule for NoUnusedFragments<'n> {
         "NoUnusedFragments"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUnusedFragments::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(NoUnusedFragments::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -1,11 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use super::ValidationRule;
-use crate::ast::{
-    visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext, ValueExtension,
-};
+use crate::ast::{AstNodeWithName, OperationVisitor, OperationVisitorContext, ValueExtension};
 use crate::static_graphql::query::{self, OperationDefinition};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// No unused fragments
 ///
@@ -16,6 +14,7 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 pub struct NoUnusedVariables<'a> {
     current_scope: Option<NoUnusedVariablesScope<'a>>,
     defined_variables: HashMap<Option<&'a str>, HashSet<&'a str>>,
+    variable_definitions: Vec<(Option<&'a str>, &'a query::VariableDefinition)>,
     used_variables: HashMap<NoUnusedVariablesScope<'a>, Vec<&'a str>>,
     spreads: HashMap<NoUnusedVariablesScope<'a>, Vec<&'a str>>,
 }
@@ -31,6 +30,7 @@ impl<'a> NoUnusedVariables<'a> {
         Self {
             current_scope: None,
             defined_variables: HashMap::new(),
+            variable_definitions: Vec::new(),
             used_variables: HashMap::new(),
             spreads: HashMap::new(),
         }
@@ -122,6 +122,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
         if let Some(NoUnusedVariablesScope::Operation(ref name)) = self.current_scope {
             if let Some(vars) = self.defined_variables.get_mut(name) {
                 vars.insert(&variable_definition.name);
+                self.variable_definitions.push((*name, variable_definition));
             }
         }
     }
@@ -146,27 +147,37 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoUnusedVariables<'a>
         user_context: &mut ValidationErrorContext,
         _: &query::Document,
     ) {
-        for (op_name, def_vars) in &self.defined_variables {
-            let mut used = HashSet::new();
-            let mut visited = HashSet::new();
-
-            self.find_used_vars(
-                &NoUnusedVariablesScope::Operation(*op_name),
-                def_vars,
-                &mut used,
-                &mut visited,
-            );
-
-            def_vars
-                .iter()
-                .filter(|var| !used.contains(*var))
-                .for_each(|var| {
-                    user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
-                        message: error_message(var, op_name),
-                        locations: vec![],
-                    })
+        let mut used_by_operation: HashMap<Option<&str>, HashSet<&str>> = HashMap::new();
+
+        for (op_name, variable_definition) in &self.variable_definitions {
+            let used = used_by_operation.entry(*op_name).or_insert_with(|| {
+                let mut used = HashSet::new();
+                let mut visited = HashSet::new();
+
+                if let Some(def_vars) = self.defined_variables.get(op_name) {
+                    self.find_used_vars(
+                        &NoUnusedVariablesScope::Operation(*op_name),
+                        def_vars,
+                        &mut used,
+                        &mut visited,
+                    );
+                }
+
+                used
+            });
+
+            if !used.contains(variable_definition.name.as_str()) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        variable_name: Some(variable_definition.name.to_string()),
+                        operation_name: op_name.map(|name| name.to_string()),
+                        ..Default::default()
+                    },
+                    message: error_message(&variable_definition.name, op_name),
+                    locations: vec![variable_definition.position],
                 })
+            }
         }
     }
 }
@@ -187,17 +198,12 @@ impl<'n> ValidationRule for NoUnusedVariables<'n> {
         "NoUnusedVariables"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut NoUnusedVariables::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(NoUnusedVariables::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -531,3 +537,40 @@ fn nested_variable_should_work_as_well() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 0);
 }
+
+#[test]
+fn reports_variable_definition_locations() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(NoUnusedVariables::new()));
+    let errors = test_operation_with_schema(
+        "query Foo($a: String, $b: String, $c: String) {
+          field(b: $b)
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Variable \"$a\" is never used in operation \"Foo\".",
+            "Variable \"$c\" is never used in operation \"Foo\"."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 1,
+            column: 11
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 1,
+            column: 35
+        }]
+    );
+}
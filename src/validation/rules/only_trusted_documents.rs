@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::Document;
+use crate::validation::trusted_documents::TrustedDocuments;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Only trusted documents
+///
+/// Rejects the documents that are not in a manifest of `TrustedDocuments`, compared in their
+/// normalized form. Add it to the plan used to validate the documents sent as text, when the
+/// server should only run persisted operations.
+#[derive(Clone, Debug)]
+pub struct OnlyTrustedDocuments {
+    trusted_documents: Arc<TrustedDocuments>,
+}
+
+impl OnlyTrustedDocuments {
+    pub fn new(trusted_documents: Arc<TrustedDocuments>) -> Self {
+        OnlyTrustedDocuments { trusted_documents }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for OnlyTrustedDocuments {
+    fn enter_document(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        document: &'a Document,
+    ) {
+        if self.trusted_documents.find(document).is_none() {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![],
+                message: "Only trusted documents are allowed.".to_string(),
+                details: Default::default(),
+            });
+        }
+    }
+}
+
+impl ValidationRule for OnlyTrustedDocuments {
+    fn error_code<'a>(&self) -> &'a str {
+        "OnlyTrustedDocuments"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self.clone())
+    }
+
+    fn fingerprint(&self) -> String {
+        format!(
+            "OnlyTrustedDocuments({:x})",
+            self.trusted_documents.fingerprint()
+        )
+    }
+}
+
+#[test]
+fn accepts_trusted_documents() {
+    use crate::validation::test_utils::*;
+
+    let trusted_documents =
+        TrustedDocuments::from_relay_manifest(r#"{ "a1": "query DogName { dog { name } }" }"#)
+            .unwrap();
+    let plan = create_plan_from_rule(Box::new(OnlyTrustedDocuments::new(Arc::new(
+        trusted_documents,
+    ))));
+    let errors = test_operation_with_schema(
+        "query DogName {
+          # the name only
+          dog {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn rejects_unknown_documents() {
+    use crate::validation::test_utils::*;
+
+    let trusted_documents =
+        TrustedDocuments::from_relay_manifest(r#"{ "a1": "query DogName { dog { name } }" }"#)
+            .unwrap();
+    let plan = create_plan_from_rule(Box::new(OnlyTrustedDocuments::new(Arc::new(
+        trusted_documents,
+    ))));
+    let errors = test_operation_with_schema(
+        "query DogName {
+          dog {
+            name
+            nickname
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Only trusted documents are allowed."]
+    );
+    assert_eq!(errors[0].error_code, "OnlyTrustedDocuments");
+}
@@ -4,14 +4,12 @@ use crate::parser::Pos;
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
 use crate::ast::{
-    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension, TypeExtension, ValueExtension,
+    OperationVisitor, OperationVisitorContext, PreparedSchema, SchemaDocumentExtension,
+    TypeExtension, ValueExtension,
 };
 use crate::static_graphql::query::*;
-use crate::static_graphql::schema::{
-    Document as SchemaDocument, Field as FieldDefinition, TypeDefinition,
-};
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::static_graphql::schema::{Field as FieldDefinition, TypeDefinition};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -219,7 +217,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // GraphQL Document.
     fn find_conflicts_within_selection_set(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
         visited_fragments: &mut Vec<&'a str>,
@@ -267,7 +265,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // Collect all Conflicts "within" one collection of fields.
     fn collect_conflicts_within(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         conflicts: &mut Vec<Conflict>,
         field_map: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
         visited_fragments: &mut Vec<&'a str>,
@@ -311,10 +309,25 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
         })
     }
 
+    // Two fields can only be merged if they are both streamed with the same arguments, or both
+    // not streamed.
+    fn is_same_stream(&self, f1_directives: &[Directive], f2_directives: &[Directive]) -> bool {
+        let stream1 = f1_directives.iter().find(|d| d.name == "stream");
+        let stream2 = f2_directives.iter().find(|d| d.name == "stream");
+
+        match (stream1, stream2) {
+            (None, None) => true,
+            (Some(stream1), Some(stream2)) => {
+                self.is_same_arguments(&stream1.arguments, &stream2.arguments)
+            }
+            _ => false,
+        }
+    }
+
     // Two types conflict if both types could not apply to a value simultaneously.
     // Composite types are ignored as their individual field types will be compared
     // later recursively. However List and Non-Null types must match.
-    fn is_type_conflict(schema: &SchemaDocument, t1: &Type, t2: &Type) -> bool {
+    fn is_type_conflict(schema: &PreparedSchema, t1: &Type, t2: &Type) -> bool {
         if let Type::ListType(t1) = t1 {
             if let Type::ListType(t2) = t2 {
                 return Self::is_type_conflict(schema, t1, t2);
@@ -355,7 +368,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // comparing their sub-fields.
     fn find_conflict(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         out_field_name: &str,
         first: &AstAndDef<'a>,
         second: &AstAndDef<'a>,
@@ -406,6 +419,22 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
                     vec![field2.position],
                 ));
             }
+
+            // Only compared when the schema supports incremental delivery.
+            if schema.directive_by_name("stream").is_some()
+                && !self.is_same_stream(&field1.directives, &field2.directives)
+            {
+                return Some(Conflict(
+                    ConflictReason(
+                        out_field_name.to_string(),
+                        ConflictReasonMessage::Message(
+                            "they have differing stream directives".to_string(),
+                        ),
+                    ),
+                    vec![field1.position],
+                    vec![field2.position],
+                ));
+            }
         }
 
         let t1 = field1_def.as_ref().map(|def| &def.field_type);
@@ -445,7 +474,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
                 &conflicts,
                 out_field_name,
                 field1.position,
-                field1.position,
+                field2.position,
             );
         }
 
@@ -474,7 +503,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
                 .collect(),
             vec![f2_pos]
                 .into_iter()
-                .chain(conflicts.iter().flat_map(|v| v.1.clone()))
+                .chain(conflicts.iter().flat_map(|v| v.2.clone()))
                 .collect(),
         ))
     }
@@ -485,7 +514,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     #[allow(clippy::too_many_arguments)]
     fn find_conflicts_between_sub_selection_sets(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         mutually_exclusive: bool,
         parent_type_name1: Option<&str>,
         selection_set1: &'a SelectionSet,
@@ -559,7 +588,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn collect_conflicts_between_fields_and_fragment(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         conflicts: &mut Vec<Conflict>,
         field_map: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
         fragment_name: &str,
@@ -609,7 +638,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // any nested fragments.
     fn collect_conflicts_between_fragments(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         conflicts: &mut Vec<Conflict>,
         fragment_name1: &'a str,
         fragment_name2: &'a str,
@@ -689,7 +718,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // as well as a list of nested fragment names referenced via fragment spreads.
     fn get_referenced_fields_and_fragment_names(
         &self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         fragment: &'a FragmentDefinition,
     ) -> (OrderedMap<&'a str, Vec<AstAndDef<'a>>>, Vec<&'a str>) {
         let TypeCondition::On(type_condition) = &fragment.type_condition;
@@ -705,7 +734,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // each individual selection set.
     fn collect_conflicts_between(
         &mut self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         conflicts: &mut Vec<Conflict>,
         mutually_exclusive: bool,
         field_map1: &OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
@@ -742,7 +771,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     // referenced via fragment spreads.
     fn get_fields_and_fragment_names(
         &self,
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
     ) -> (OrderedMap<&'a str, Vec<AstAndDef<'a>>>, Vec<&'a str>) {
@@ -761,7 +790,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     }
 
     fn collect_fields_and_fragment_names(
-        schema: &'a SchemaDocument,
+        schema: &'a PreparedSchema<'a>,
         parent_type: Option<&'a TypeDefinition>,
         selection_set: &'a SelectionSet,
         ast_and_defs: &mut OrderedMap<&'a str, Vec<AstAndDef<'a>>>,
@@ -771,7 +800,8 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
             match selection {
                 Selection::Field(field) => {
                     let field_name = &field.name;
-                    let field_def = parent_type.and_then(|t| t.field_by_name(field_name));
+                    let field_def =
+                        parent_type.and_then(|t| schema.field_by_name(t.name(), field_name));
                     let out_field_name = field.alias.as_ref().unwrap_or(field_name).as_str();
 
                     if !ast_and_defs.contains_key(out_field_name) {
@@ -849,7 +879,11 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for OverlappingFieldsCanBe
             p1.extend(p2);
 
             user_context.report_error(ValidationError {
-                error_code: self.error_code(),
+                error_code: self.error_code().into(),
+                details: ValidationErrorDetails {
+                    field_name: Some(reason_name.to_string()),
+                    ..Default::default()
+                },
                 message: error_message(&reason_name, &reason_msg),
                 locations: p1,
             });
@@ -890,17 +924,12 @@ impl<'o> ValidationRule for OverlappingFieldsCanBeMerged<'o> {
         "OverlappingFieldsCanBeMerged"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut OverlappingFieldsCanBeMerged::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(OverlappingFieldsCanBeMerged::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -1263,6 +1292,27 @@ fn deep_conflict() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages, vec!["Fields \"field\" conflict because subfields \"x\" conflict because \"a\" and \"b\" are different fields. Use different aliases on the fields to fetch both if this was intentional."]);
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 2,
+                column: 11
+            },
+            Pos {
+                line: 3,
+                column: 13
+            },
+            Pos {
+                line: 5,
+                column: 11
+            },
+            Pos {
+                line: 6,
+                column: 13
+            },
+        ]
+    );
 }
 
 #[test]
@@ -1843,3 +1893,76 @@ fn finds_invalid_case_even_with_immediately_recursive_fragment() {
       "Fields \"fido\" conflict because \"name\" and \"nickname\" are different fields. Use different aliases on the fields to fetch both if this was intentional."
     ]);
 }
+
+#[test]
+fn same_stream_directives() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(OverlappingFieldsCanBeMerged::new()));
+    let errors = test_operation_with_schema(
+        "{
+          messages @stream(label: \"messages\", initialCount: 1) {
+            body
+          }
+          messages @stream(initialCount: 1, label: \"messages\") {
+            sender
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 0);
+}
+
+#[test]
+fn differing_stream_directives() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(OverlappingFieldsCanBeMerged::new()));
+    let errors = test_operation_with_schema(
+        "{
+          messages @stream(initialCount: 1) {
+            body
+          }
+          messages @stream(initialCount: 2) {
+            body
+          }
+          nonNullMessages @stream {
+            body
+          }
+          nonNullMessages {
+            body
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages, vec![
+      "Fields \"messages\" conflict because they have differing stream directives. Use different aliases on the fields to fetch both if this was intentional.",
+      "Fields \"nonNullMessages\" conflict because they have differing stream directives. Use different aliases on the fields to fetch both if this was intentional."
+    ]);
+}
+
+#[test]
+fn stream_directives_are_ignored_without_stream_support() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(OverlappingFieldsCanBeMerged::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name @stream
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 0);
+}
@@ -1,12 +1,12 @@
 use super::ValidationRule;
 use crate::ast::ext::TypeDefinitionExtension;
 use crate::ast::{
-    visit_document, ImplementingInterfaceExtension, OperationVisitor, OperationVisitorContext,
-    PossibleTypesExtension, SchemaDocumentExtension,
+    ImplementingInterfaceExtension, OperationVisitor, OperationVisitorContext, PreparedSchema,
+    SchemaDocumentExtension,
 };
 use crate::static_graphql::query::TypeCondition;
 use crate::static_graphql::schema;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Possible fragment spread
 ///
@@ -39,7 +39,7 @@ impl PossibleFragmentSpreads {
  * This function is commutative.
  */
 pub fn do_types_overlap(
-    schema: &schema::Document,
+    schema: &PreparedSchema,
     t1: &schema::TypeDefinition,
     t2: &schema::TypeDefinition,
 ) -> bool {
@@ -49,7 +49,7 @@ pub fn do_types_overlap(
 
     if t1.is_abstract_type() {
         if t2.is_abstract_type() {
-            let possible_types = t1.possible_types(schema);
+            let possible_types = schema.possible_types(t1);
 
             return possible_types
                 .into_iter()
@@ -73,7 +73,7 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for PossibleFragmentSpread
         &mut self,
         visitor_context: &mut OperationVisitorContext,
         user_context: &mut ValidationErrorContext,
-        _inline_fragment: &crate::static_graphql::query::InlineFragment,
+        inline_fragment: &crate::static_graphql::query::InlineFragment,
     ) {
         if let Some(frag_schema_type) = visitor_context.current_type() {
             if let Some(parent_type) = visitor_context.current_parent_type() {
@@ -81,8 +81,9 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for PossibleFragmentSpread
                     && parent_type.is_composite_type()
                     && !do_types_overlap(visitor_context.schema, frag_schema_type, parent_type)
                 {
-                    user_context.report_error(ValidationError {error_code: self.error_code(),
-                      locations: vec![],
+                    user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                      details: ValidationErrorDetails { type_name: Some(frag_schema_type.name().to_string()), ..Default::default() },
+                      locations: vec![inline_fragment.position],
                       message: format!("Fragment cannot be spread here as objects of type \"{}\" can never be of type \"{}\".", parent_type.name(), frag_schema_type.name()),
                     })
                 }
@@ -108,8 +109,9 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for PossibleFragmentSpread
                         && parent_type.is_composite_type()
                         && !do_types_overlap(visitor_context.schema, fragment_type, parent_type)
                     {
-                        user_context.report_error(ValidationError {error_code: self.error_code(),
-                        locations: vec![],
+                        user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                          details: ValidationErrorDetails { type_name: Some(fragment_type_name.to_string()), fragment_name: Some(actual_fragment.name.to_string()), ..Default::default() },
+                        locations: vec![fragment_spread.position],
                         message: format!("Fragment \"{}\" cannot be spread here as objects of type \"{}\" can never be of type \"{}\".", actual_fragment.name, parent_type.name(), fragment_type_name),
                       })
                     }
@@ -124,17 +126,12 @@ impl ValidationRule for PossibleFragmentSpreads {
         "PossibleFragmentSpreads"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut PossibleFragmentSpreads::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(PossibleFragmentSpreads::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
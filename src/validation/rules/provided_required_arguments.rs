@@ -1,11 +1,11 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, FieldByNameExtension, InputValueHelpers, OperationVisitor,
-    OperationVisitorContext,
+    InputValueHelpers, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
+    TypeDefinitionExtension,
 };
 use crate::static_graphql::query::Value;
 use crate::static_graphql::schema::InputValue;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Provided required arguments
 ///
@@ -35,12 +35,16 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ProvidedRequiredArgume
         field: &crate::static_graphql::query::Field,
     ) {
         if let Some(parent_type) = visitor_context.current_parent_type() {
-            if let Some(field_def) = parent_type.field_by_name(&field.name) {
+            if let Some(field_def) = visitor_context
+                .schema
+                .field_by_name(parent_type.name(), &field.name)
+            {
                 let missing_required_args =
                     validate_arguments(&field.arguments, &field_def.arguments);
 
                 for missing in missing_required_args {
-                    user_context.report_error(ValidationError {error_code: self.error_code(),
+                    user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                      details: ValidationErrorDetails { type_name: Some(parent_type.name().to_string()), field_name: Some(field.name.to_string()), argument_name: Some(missing.name.to_string()), ..Default::default() },
               locations: vec![field.position],
               message: format!("Field \"{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
               field.name, missing.name, missing.value_type),
@@ -56,14 +60,13 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ProvidedRequiredArgume
         user_context: &mut ValidationErrorContext,
         directive: &crate::static_graphql::query::Directive,
     ) {
-        let known_directives = &visitor_context.directives;
-
-        if let Some(directive_def) = known_directives.get(&directive.name) {
+        if let Some(directive_def) = visitor_context.schema.directive_by_name(&directive.name) {
             let missing_required_args =
                 validate_arguments(&directive.arguments, &directive_def.arguments);
 
             for missing in missing_required_args {
-                user_context.report_error(ValidationError {error_code: self.error_code(),
+                user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                  details: ValidationErrorDetails { directive_name: Some(directive.name.to_string()), argument_name: Some(missing.name.to_string()), ..Default::default() },
               locations: vec![directive.position],
               message: format!("Directive \"@{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
               directive.name, missing.name, missing.value_type),
@@ -98,17 +101,12 @@ impl ValidationRule for ProvidedRequiredArguments {
         "ProvidedRequiredArguments"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut ProvidedRequiredArguments::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(ProvidedRequiredArguments::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -438,3 +436,24 @@ fn with_directive_with_missing_types() {
       "Directive \"@skip\" argument \"if\" of type \"Boolean!\" is required, but it was not provided."
     ])
 }
+
+#[test]
+fn missing_argument_on_type_meta_field() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(ProvidedRequiredArguments {}));
+    let errors = test_operation_with_schema(
+        "{
+          __type {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Field \"__type\" argument \"name\" of type \"String!\" is required, but it was not provided."]
+    );
+}
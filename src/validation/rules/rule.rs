@@ -1,11 +1,29 @@
-use crate::{ast::OperationVisitorContext, validation::utils::ValidationErrorContext};
+use crate::{
+    ast::{visit_document, OperationVisitor, OperationVisitorContext},
+    validation::utils::ValidationErrorContext,
+};
 
 pub trait ValidationRule: Send + Sync {
-    fn validate(
-        &self,
-        _ctx: &mut OperationVisitorContext<'_>,
-        _error_collector: &mut ValidationErrorContext,
-    );
+    /// Creates a fresh visitor for a single validation run.
+    ///
+    /// `ValidationPlan` collects the visitors of all its rules and drives them in one
+    /// traversal of the operation, so rules should only hold per-run state in the visitor.
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a>;
+
+    /// Runs this rule alone, with a dedicated traversal of the operation.
+    fn validate<'a>(
+        &'a self,
+        ctx: &mut OperationVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(self.visitor().as_mut(), ctx.operation, ctx, error_collector);
+    }
 
     fn error_code<'a>(&self) -> &'a str;
+
+    /// Identifies this rule in `ValidationPlan::fingerprint`, which keys `ValidationCache`.
+    ///
+    /// Must include every option that changes the outcome of the rule, so plans with different
+    /// options don't share cached results. Rules without options return their `error_code`.
+    fn fingerprint(&self) -> String;
 }
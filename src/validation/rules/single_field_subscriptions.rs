@@ -1,11 +1,10 @@
 use super::ValidationRule;
 use crate::ast::{
-    collect_fields, visit_document, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension,
+    collect_fields, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
 };
 use crate::static_graphql::query::OperationDefinition;
 use crate::static_graphql::schema::TypeDefinition;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Unique operation names
 ///
@@ -55,7 +54,11 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
                     };
 
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            operation_name: operation_name.map(|name| name.to_string()),
+                            ..Default::default()
+                        },
                         locations: vec![subscription.position],
                         message: error_message,
                     });
@@ -80,7 +83,8 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
                           .to_owned(),
                   };
 
-                  user_context.report_error(ValidationError {error_code: self.error_code(),
+                  user_context.report_error(ValidationError {error_code: self.error_code().into(),
+                    details: ValidationErrorDetails { operation_name: operation_name.map(|name| name.to_string()), ..Default::default() },
                     locations: vec![subscription.position],
                     message: error_message,
                 });
@@ -95,17 +99,12 @@ impl ValidationRule for SingleFieldSubscriptions {
         "SingleFieldSubscriptions"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut SingleFieldSubscriptions::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(SingleFieldSubscriptions::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
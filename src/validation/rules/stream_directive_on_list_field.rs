@@ -0,0 +1,133 @@
+use super::ValidationRule;
+use crate::ast::{OperationVisitor, OperationVisitorContext, TypeDefinitionExtension};
+use crate::static_graphql::query::{Directive, Type};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
+
+/// Stream directive on list field
+///
+/// A GraphQL document is only valid if `@stream` is used on fields returning a list.
+///
+/// See https://github.com/graphql/graphql-js/blob/main/src/validation/rules/StreamDirectiveOnListFieldRule.ts
+pub struct StreamDirectiveOnListField;
+
+impl Default for StreamDirectiveOnListField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamDirectiveOnListField {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn is_list(field_type: &Type) -> bool {
+    match field_type {
+        Type::ListType(_) => true,
+        Type::NonNullType(inner_type) => matches!(**inner_type, Type::ListType(_)),
+        Type::NamedType(_) => false,
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for StreamDirectiveOnListField {
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        if directive.name != "stream" {
+            return;
+        }
+
+        let Some((parent_type, field_def)) = visitor_context
+            .current_parent_type()
+            .zip(visitor_context.current_field())
+        else {
+            return;
+        };
+
+        if !is_list(&field_def.field_type) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                locations: vec![directive.position],
+                message: format!(
+                    "Directive \"@stream\" cannot be used on non-list field \"{}.{}\".",
+                    parent_type.name(),
+                    field_def.name
+                ),
+                details: ValidationErrorDetails {
+                    directive_name: Some(directive.name.clone()),
+                    type_name: Some(parent_type.name().to_string()),
+                    field_name: Some(field_def.name.clone()),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+impl ValidationRule for StreamDirectiveOnListField {
+    fn error_code<'a>(&self) -> &'a str {
+        "StreamDirectiveOnListField"
+    }
+
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(StreamDirectiveOnListField::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
+    }
+}
+
+#[test]
+fn stream_on_list_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(StreamDirectiveOnListField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          messages @stream(initialCount: 1) {
+            body
+          }
+          nonNullMessages @stream {
+            body
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn stream_on_non_list_field() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(StreamDirectiveOnListField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          message {
+            body @stream
+          }
+        }",
+        DEFER_STREAM_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Directive \"@stream\" cannot be used on non-list field \"Message.body\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 3,
+            column: 18
+        }]
+    );
+}
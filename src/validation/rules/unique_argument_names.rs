@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use crate::parser::Pos;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::ast::{OperationVisitor, OperationVisitorContext};
 use crate::static_graphql::query::Value;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Unique argument names
 ///
@@ -39,7 +39,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueArgumentNames {
         found_args.iter().for_each(|(arg_name, positions)| {
             if positions.len() > 1 {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        field_name: Some(field.name.to_string()),
+                        argument_name: Some(arg_name.to_string()),
+                        ..Default::default()
+                    },
                     message: format!("There can be only one argument named \"{}\".", arg_name),
                     locations: positions.clone(),
                 })
@@ -58,7 +63,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueArgumentNames {
         found_args.iter().for_each(|(arg_name, positions)| {
             if positions.len() > 1 {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        directive_name: Some(directive.name.to_string()),
+                        argument_name: Some(arg_name.to_string()),
+                        ..Default::default()
+                    },
                     message: format!("There can be only one argument named \"{}\".", arg_name),
                     locations: positions.clone(),
                 })
@@ -88,17 +98,12 @@ impl ValidationRule for UniqueArgumentNames {
         "UniqueArgumentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut UniqueArgumentNames::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(UniqueArgumentNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
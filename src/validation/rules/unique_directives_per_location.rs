@@ -1,13 +1,13 @@
 use std::collections::HashSet;
 
 use super::ValidationRule;
-use crate::ast::OperationDefinitionExtension;
+use crate::ast::{OperationDefinitionExtension, SchemaDocumentExtension};
 use crate::static_graphql::query::{
     Directive, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
 };
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext},
-    validation::utils::{ValidationError, ValidationErrorContext},
+    ast::{OperationVisitor, OperationVisitorContext},
+    validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails},
 };
 
 /// Unique directive names per location
@@ -38,11 +38,15 @@ impl UniqueDirectivesPerLocation {
         let mut exists = HashSet::new();
 
         for directive in directives {
-            if let Some(meta_directive) = ctx.directives.get(&directive.name) {
+            if let Some(meta_directive) = ctx.schema.directive_by_name(&directive.name) {
                 if !meta_directive.repeatable {
                     if exists.contains(&directive.name) {
                         err_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails {
+                                directive_name: Some(directive.name.to_string()),
+                                ..Default::default()
+                            },
                             locations: vec![directive.position],
                             message: format!("Duplicate directive \"{}\"", &directive.name),
                         });
@@ -109,17 +113,12 @@ impl ValidationRule for UniqueDirectivesPerLocation {
         "UniqueDirectivesPerLocation"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut UniqueDirectivesPerLocation::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(UniqueDirectivesPerLocation::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
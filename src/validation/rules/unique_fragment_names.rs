@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::ast::{AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::parser::Pos;
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Unique fragment names
 ///
@@ -11,7 +12,7 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 ///
 /// See https://spec.graphql.org/draft/#sec-Fragment-Name-Uniqueness
 pub struct UniqueFragmentNames<'a> {
-    findings_counter: HashMap<&'a str, i32>,
+    findings_counter: HashMap<&'a str, Vec<Pos>>,
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueFragmentNames<'a> {
@@ -22,9 +23,37 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueFragmentNames<'a
         fragment: &'a FragmentDefinition,
     ) {
         if let Some(name) = fragment.node_name() {
-            self.store_finding(name);
+            self.store_finding(name, fragment.position);
         }
     }
+
+    fn leave_document(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        _: &Document,
+    ) {
+        let error_code = self.error_code();
+
+        let mut duplicates = self
+            .findings_counter
+            .iter()
+            .filter(|(_key, positions)| positions.len() > 1)
+            .collect::<Vec<_>>();
+        duplicates.sort_by_key(|(_key, positions)| positions[0]);
+
+        duplicates.into_iter().for_each(|(key, positions)| {
+            user_context.report_error(ValidationError {
+                error_code: error_code.into(),
+                details: ValidationErrorDetails {
+                    fragment_name: Some(key.to_string()),
+                    ..Default::default()
+                },
+                message: format!("There can be only one fragment named \"{}\".", key),
+                locations: positions.clone(),
+            })
+        })
+    }
 }
 
 impl<'a> Default for UniqueFragmentNames<'a> {
@@ -40,9 +69,11 @@ impl<'a> UniqueFragmentNames<'a> {
         }
     }
 
-    fn store_finding(&mut self, name: &'a str) {
-        let value = *self.findings_counter.entry(name).or_insert(0);
-        self.findings_counter.insert(name, value + 1);
+    fn store_finding(&mut self, name: &'a str, position: Pos) {
+        self.findings_counter
+            .entry(name)
+            .or_default()
+            .push(position);
     }
 }
 
@@ -51,25 +82,12 @@ impl<'u> ValidationRule for UniqueFragmentNames<'u> {
         "UniqueFragmentNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        let mut rule = UniqueFragmentNames::new();
-
-        visit_document(&mut rule, ctx.operation, ctx, error_collector);
-
-        rule.findings_counter
-            .into_iter()
-            .filter(|(_key, value)| *value > 1)
-            .for_each(|(key, _value)| {
-                error_collector.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    message: format!("There can be only one fragment named \"{}\".", key),
-                    locations: vec![],
-                })
-            })
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(UniqueFragmentNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
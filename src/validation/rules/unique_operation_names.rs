@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
 use super::ValidationRule;
-use crate::ast::{visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext};
+use crate::ast::{
+    AstNodeWithName, OperationDefinitionExtension, OperationVisitor, OperationVisitorContext,
+};
+use crate::parser::Pos;
 use crate::static_graphql::query::*;
-use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use crate::validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails};
 
 /// Unique operation names
 ///
@@ -11,7 +14,7 @@ use crate::validation::utils::{ValidationError, ValidationErrorContext};
 ///
 /// See https://spec.graphql.org/draft/#sec-Operation-Name-Uniqueness
 pub struct UniqueOperationNames<'a> {
-    findings_counter: HashMap<&'a str, i32>,
+    findings_counter: HashMap<&'a str, Vec<Pos>>,
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueOperationNames<'a> {
@@ -22,9 +25,37 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for UniqueOperationNames<'
         operation_definition: &'a OperationDefinition,
     ) {
         if let Some(name) = operation_definition.node_name() {
-            self.store_finding(name);
+            self.store_finding(name, operation_definition.position());
         }
     }
+
+    fn leave_document(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        _: &Document,
+    ) {
+        let error_code = self.error_code();
+
+        let mut duplicates = self
+            .findings_counter
+            .iter()
+            .filter(|(_key, positions)| positions.len() > 1)
+            .collect::<Vec<_>>();
+        duplicates.sort_by_key(|(_key, positions)| positions[0]);
+
+        duplicates.into_iter().for_each(|(key, positions)| {
+            user_context.report_error(ValidationError {
+                error_code: error_code.into(),
+                details: ValidationErrorDetails {
+                    operation_name: Some(key.to_string()),
+                    ..Default::default()
+                },
+                message: format!("There can be only one operation named \"{}\".", key),
+                locations: positions.clone(),
+            })
+        })
+    }
 }
 
 impl<'a> Default for UniqueOperationNames<'a> {
@@ -40,9 +71,11 @@ impl<'a> UniqueOperationNames<'a> {
         }
     }
 
-    fn store_finding(&mut self, name: &'a str) {
-        let value = *self.findings_counter.entry(name).or_insert(0);
-        self.findings_counter.insert(name, value + 1);
+    fn store_finding(&mut self, name: &'a str, position: Pos) {
+        self.findings_counter
+            .entry(name)
+            .or_default()
+            .push(position);
     }
 }
 
@@ -51,25 +84,12 @@ impl<'u> ValidationRule for UniqueOperationNames<'u> {
         "UniqueOperationNames"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        let mut rule = UniqueOperationNames::new();
-
-        visit_document(&mut rule, ctx.operation, ctx, error_collector);
-
-        rule.findings_counter
-            .into_iter()
-            .filter(|(_key, value)| *value > 1)
-            .for_each(|(key, _value)| {
-                error_collector.report_error(ValidationError {
-                    error_code: self.error_code(),
-                    message: format!("There can be only one operation named \"{}\".", key),
-                    locations: vec![],
-                })
-            })
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(UniqueOperationNames::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -208,6 +228,10 @@ fn multiple_operations_of_same_name() {
         messages,
         vec!["There can be only one operation named \"Foo\".",]
     );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos { line: 1, column: 1 }, Pos { line: 4, column: 9 }]
+    );
 }
 
 #[test]
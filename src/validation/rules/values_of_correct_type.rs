@@ -1,20 +1,54 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 use crate::parser::schema::TypeDefinition;
+use crate::parser::Pos;
 
 use crate::ast::{
     InputValueHelpers, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
 };
-use crate::static_graphql::query::Value;
+use crate::static_graphql::query::{Type, Value};
+use crate::static_graphql::schema::ScalarType;
+use crate::validation::scalars::{specified_by_validators, ScalarValidator};
 use crate::validation::utils::ValidationError;
+use crate::validation::utils::ValidationErrorDetails;
 use crate::{
-    ast::{visit_document, OperationVisitor, OperationVisitorContext},
+    ast::{OperationVisitor, OperationVisitorContext},
     validation::utils::ValidationErrorContext,
 };
 
 use super::ValidationRule;
 
-pub struct ValuesOfCorrectType {}
+/// Values of correct type
+///
+/// A GraphQL document is only valid if all value literals are of the type
+/// expected at their position.
+///
+/// Custom scalars accept any literal, unless a `ScalarValidator` is registered for them, by name
+/// or by the `@specifiedBy` URL of their definition.
+///
+/// See https://spec.graphql.org/draft/#sec-Values-of-Correct-Type
+#[derive(Clone)]
+pub struct ValuesOfCorrectType {
+    scalar_validators: HashMap<String, RegisteredValidator>,
+    specified_by_validators: HashMap<String, RegisteredValidator>,
+}
+
+/// A validator and the id that identifies it in `ValidationRule::fingerprint`.
+#[derive(Clone)]
+struct RegisteredValidator {
+    id: String,
+    validator: Arc<dyn ScalarValidator>,
+}
+
+impl RegisteredValidator {
+    fn new(id: impl Into<String>, validator: Arc<dyn ScalarValidator>) -> Self {
+        Self {
+            id: id.into(),
+            validator,
+        }
+    }
+}
 
 impl Default for ValuesOfCorrectType {
     fn default() -> Self {
@@ -24,15 +58,172 @@ impl Default for ValuesOfCorrectType {
 
 impl ValuesOfCorrectType {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            scalar_validators: HashMap::new(),
+            specified_by_validators: HashMap::new(),
+        }
+    }
+
+    /// Validates the literals of the custom scalar named `scalar_name` with `validator`.
+    ///
+    /// `id` identifies the validator in the rule's fingerprint: give distinct ids to validators
+    /// that accept different literals, or cached validation results will be shared between them.
+    pub fn with_scalar_validator(
+        mut self,
+        scalar_name: impl Into<String>,
+        id: impl Into<String>,
+        validator: impl ScalarValidator + 'static,
+    ) -> Self {
+        self.scalar_validators.insert(
+            scalar_name.into(),
+            RegisteredValidator::new(id, Arc::new(validator)),
+        );
+        self
+    }
+
+    /// Validates the literals of the custom scalars defined with `@specifiedBy(url: ...)` and
+    /// this URL with `validator`. Validators registered by scalar name take precedence.
+    ///
+    /// `id` identifies the validator in the rule's fingerprint, like in `with_scalar_validator`.
+    pub fn with_specified_by_validator(
+        mut self,
+        url: impl Into<String>,
+        id: impl Into<String>,
+        validator: impl ScalarValidator + 'static,
+    ) -> Self {
+        self.specified_by_validators.insert(
+            url.into(),
+            RegisteredValidator::new(id, Arc::new(validator)),
+        );
+        self
+    }
+
+    /// Registers the validators of `validation::scalars` by their `@specifiedBy` URL.
+    pub fn with_builtin_scalar_validators(mut self) -> Self {
+        for (url, validator) in specified_by_validators() {
+            self.specified_by_validators
+                .entry(url.to_string())
+                .or_insert_with(|| RegisteredValidator::new(format!("builtin:{}", url), validator));
+        }
+        self
     }
 
     pub fn is_custom_scalar(&self, type_name: &str) -> bool {
         !matches!(type_name, "String" | "Int" | "Float" | "Boolean" | "ID")
     }
 
+    fn scalar_validator(&self, scalar_type_def: &ScalarType) -> Option<&dyn ScalarValidator> {
+        if let Some(registered) = self.scalar_validators.get(&scalar_type_def.name) {
+            return Some(registered.validator.as_ref());
+        }
+
+        scalar_type_def
+            .directives
+            .iter()
+            .filter(|directive| directive.name == "specifiedBy")
+            .flat_map(|directive| &directive.arguments)
+            .find_map(|(name, value)| match (name.as_str(), value) {
+                ("url", Value::String(url)) => self.specified_by_validators.get(url),
+                _ => None,
+            })
+            .map(|registered| registered.validator.as_ref())
+    }
+
+    fn validate_custom_scalar(
+        &self,
+        visitor_context: &OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        scalar_type_def: &ScalarType,
+        value: &Value,
+    ) {
+        if let Some(validator) = self.scalar_validator(scalar_type_def) {
+            if let Err(reason) = validator.validate(value) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        type_name: Some(scalar_type_def.name.to_string()),
+                        ..Default::default()
+                    },
+                    message: format!(
+                        "Expected value of type \"{}\", found {}; {}",
+                        scalar_type_def.name, value, reason
+                    ),
+                    locations: visitor_context.current_position().into_iter().collect(),
+                })
+            }
+        }
+    }
+
+    /// `@oneOf` input objects must have exactly one field, which is not `null`.
+    fn validate_one_of_input_object(
+        &self,
+        user_context: &mut ValidationErrorContext,
+        type_name: &str,
+        object_value: &BTreeMap<String, Value>,
+        locations: Vec<Pos>,
+    ) {
+        let mut fields = object_value.iter();
+
+        let error = match (fields.next(), fields.next()) {
+            (Some((field_name, Value::Null)), None) => Some((
+                format!("Field \"{}.{}\" must be non-null.", type_name, field_name),
+                Some(field_name.clone()),
+            )),
+            (Some(_), None) => None,
+            _ => Some((
+                format!(
+                    "OneOf Input Object \"{}\" must specify exactly one key.",
+                    type_name
+                ),
+                None,
+            )),
+        };
+
+        if let Some((message, field_name)) = error {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code().into(),
+                details: ValidationErrorDetails {
+                    type_name: Some(type_name.to_string()),
+                    field_name,
+                    ..Default::default()
+                },
+                message,
+                locations,
+            });
+        }
+    }
+
+    /// Custom scalars may accept list and object literals, which are not visited as scalar values.
+    fn validate_composite_literal(
+        &self,
+        visitor_context: &OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        value: impl FnOnce() -> Value,
+    ) {
+        let named_type = match visitor_context.current_input_type_literal() {
+            Some(Type::NonNullType(inner)) => inner.as_ref(),
+            Some(input_type) => input_type,
+            None => return,
+        };
+
+        if let Type::NamedType(type_name) = named_type {
+            if let Some(TypeDefinition::Scalar(scalar_type_def)) =
+                visitor_context.schema.type_by_name(type_name)
+            {
+                if self.is_custom_scalar(&scalar_type_def.name) {
+                    self.validate_custom_scalar(
+                        visitor_context,
+                        user_context,
+                        scalar_type_def,
+                        &value(),
+                    );
+                }
+            }
+        }
+    }
+
     pub fn validate_value(
-        &mut self,
+        &self,
         visitor_context: &mut OperationVisitorContext,
         user_context: &mut ValidationErrorContext,
         raw_value: &Value,
@@ -43,12 +234,16 @@ impl ValuesOfCorrectType {
             if let Some(type_def) = visitor_context.schema.type_by_name(named_type) {
                 if !type_def.is_leaf_type() {
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(named_type.to_string()),
+                            ..Default::default()
+                        },
                         message: format!(
                             "Expected value of type \"{}\", found {}.",
                             named_type, raw_value
                         ),
-                        locations: vec![],
+                        locations: visitor_context.current_position().into_iter().collect(),
                     })
                 }
 
@@ -63,16 +258,26 @@ impl ValuesOfCorrectType {
                         | ("String", Value::String(_)) => return,
                         (expected, value) => {
                             if self.is_custom_scalar(expected) {
+                                self.validate_custom_scalar(
+                                    visitor_context,
+                                    user_context,
+                                    scalar_type_def,
+                                    value,
+                                );
                                 return;
                             }
 
                             user_context.report_error(ValidationError {
-                                error_code: self.error_code(),
+                                error_code: self.error_code().into(),
+                                details: ValidationErrorDetails {
+                                    type_name: Some(expected.to_string()),
+                                    ..Default::default()
+                                },
                                 message: format!(
                                     "Expected value of type \"{}\", found {}.",
                                     expected, value
                                 ),
-                                locations: vec![],
+                                locations: visitor_context.current_position().into_iter().collect(),
                             })
                         }
                     }
@@ -83,22 +288,33 @@ impl ValuesOfCorrectType {
                         Value::Enum(enum_value) => {
                             if !enum_type_def.values.iter().any(|v| v.name.eq(enum_value)) {
                                 user_context.report_error(ValidationError {
-                                    error_code: self.error_code(),
+                                    error_code: self.error_code().into(),
+                                    details: ValidationErrorDetails {
+                                        type_name: Some(enum_type_def.name.to_string()),
+                                        ..Default::default()
+                                    },
                                     message: format!(
                                         "Value \"{}\" does not exist in \"{}\" enum.",
                                         enum_value, enum_type_def.name
                                     ),
-                                    locations: vec![],
+                                    locations: visitor_context
+                                        .current_position()
+                                        .into_iter()
+                                        .collect(),
                                 })
                             }
                         }
                         value => user_context.report_error(ValidationError {
-                            error_code: self.error_code(),
+                            error_code: self.error_code().into(),
+                            details: ValidationErrorDetails {
+                                type_name: Some(enum_type_def.name.to_string()),
+                                ..Default::default()
+                            },
                             message: format!(
                                 "Enum \"{}\" cannot represent non-enum value: {}",
                                 enum_type_def.name, value
                             ),
-                            locations: vec![],
+                            locations: visitor_context.current_position().into_iter().collect(),
                         }),
                     }
                 }
@@ -107,7 +323,8 @@ impl ValuesOfCorrectType {
     }
 }
 
-impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
+/// The visitor borrows the rule, so its validators aren't cloned for every validation.
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for &'a ValuesOfCorrectType {
     fn enter_null_value(
         &mut self,
         visitor_context: &mut OperationVisitorContext<'a>,
@@ -117,32 +334,56 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
         if let Some(input_type) = visitor_context.current_input_type_literal() {
             if input_type.is_non_null() {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        type_name: Some(input_type.inner_type().to_string()),
+                        ..Default::default()
+                    },
                     message: format!("Expected value of type \"{}\", found null", input_type),
-                    locations: vec![],
+                    locations: visitor_context.current_position().into_iter().collect(),
                 })
             }
         }
     }
 
+    fn enter_list_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        list_value: &Vec<Value>,
+    ) {
+        self.validate_composite_literal(visitor_context, user_context, || {
+            Value::List(list_value.clone())
+        });
+    }
+
     fn enter_object_value(
         &mut self,
         visitor_context: &mut OperationVisitorContext<'a>,
         user_context: &mut ValidationErrorContext,
         object_value: &BTreeMap<String, Value>,
     ) {
-        if let Some(TypeDefinition::InputObject(input_object_def)) =
+        self.validate_composite_literal(visitor_context, user_context, || {
+            Value::Object(object_value.clone())
+        });
+
+        if let Some(input_type @ TypeDefinition::InputObject(input_object_def)) =
             visitor_context.current_input_type()
         {
             input_object_def.fields.iter().for_each(|field| {
                 if field.is_required() && !object_value.contains_key(&field.name) {
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(input_object_def.name.to_string()),
+                            field_name: Some(field.name.to_string()),
+                            ..Default::default()
+                        },
                         message: format!(
                             "Field \"{}.{}\" of required type \"{}\" was not provided.",
                             input_object_def.name, field.name, field.value_type
                         ),
-                        locations: vec![],
+                        locations: visitor_context.current_position().into_iter().collect(),
                     })
                 }
             });
@@ -154,15 +395,29 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
                     .any(|f| f.name.eq(field_name))
                 {
                     user_context.report_error(ValidationError {
-                        error_code: self.error_code(),
+                        error_code: self.error_code().into(),
+                        details: ValidationErrorDetails {
+                            type_name: Some(input_object_def.name.to_string()),
+                            field_name: Some(field_name.to_string()),
+                            ..Default::default()
+                        },
                         message: format!(
                             "Field \"{}\" is not defined by type \"{}\".",
                             field_name, input_object_def.name
                         ),
-                        locations: vec![],
+                        locations: visitor_context.current_position().into_iter().collect(),
                     })
                 }
             });
+
+            if input_type.is_one_of_input_type() {
+                self.validate_one_of_input_object(
+                    user_context,
+                    &input_object_def.name,
+                    object_value,
+                    visitor_context.current_position().into_iter().collect(),
+                );
+            }
         }
     }
 
@@ -190,17 +445,26 @@ impl ValidationRule for ValuesOfCorrectType {
         "ValuesOfCorrectType"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut ValuesOfCorrectType::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(self)
+    }
+
+    fn fingerprint(&self) -> String {
+        let ids = |validators: &HashMap<String, RegisteredValidator>| {
+            validators
+                .iter()
+                .map(|(key, registered)| (key.clone(), registered.id.clone()))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let scalars = ids(&self.scalar_validators);
+        let urls = ids(&self.specified_by_validators);
+
+        format!(
+            "{}(scalars: {:?}, specifiedBy: {:?})",
+            self.error_code(),
+            scalars,
+            urls
+        )
     }
 }
 
@@ -1963,3 +2227,208 @@ fn list_variables_with_invalid_item() {
         vec!["Expected value of type \"String\", found 2."]
     );
 }
+
+#[test]
+fn reports_location_of_the_argument_owner() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "query ($var: Int = \"abc\") {
+          complicatedArgs {
+            complexArgField(complexArg: { requiredField: true, unknownField: 1 })
+            intArgField(intArg: \"3\") @include(if: 1)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 4);
+    assert_eq!(errors[0].locations, vec![Pos { line: 1, column: 8 }]);
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 3,
+            column: 13
+        }]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![Pos {
+            line: 4,
+            column: 13
+        }]
+    );
+    assert_eq!(
+        errors[3].locations,
+        vec![Pos {
+            line: 4,
+            column: 38
+        }]
+    );
+}
+
+#[cfg(test)]
+static CUSTOM_SCALARS_TEST_SCHEMA: &str = r#"
+scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")
+scalar UUID
+scalar JSON
+
+type Query {
+  events(after: DateTime, id: UUID, ids: [UUID!], filter: JSON): [String]
+}
+"#;
+
+#[test]
+fn custom_scalars_accept_any_literal_by_default() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        r#"{ events(after: 10, id: true, filter: { a: [1, "b"] }) }"#,
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn custom_scalar_validators_by_name() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(
+        ValuesOfCorrectType::new()
+            .with_scalar_validator("UUID", "uuid", crate::validation::scalars::UuidValidator)
+            .with_scalar_validator("JSON", "json-object", |value: &Value| match value {
+                Value::Object(_) => Ok(()),
+                _ => Err("JSON filters must be objects".to_string()),
+            }),
+    ));
+    let errors = test_operation_with_schema(
+        r#"{
+          valid: events(id: "123e4567-e89b-12d3-a456-426614174000", filter: { a: 1 })
+          invalid: events(id: "123", ids: ["123e4567-e89b-12d3-a456-426614174000", "abc"], filter: [1])
+        }"#,
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Expected value of type \"UUID\", found \"123\"; UUID cannot represent an invalid UUID \"123\".",
+            "Expected value of type \"UUID\", found \"abc\"; UUID cannot represent an invalid UUID \"abc\".",
+            "Expected value of type \"JSON\", found [1]; JSON filters must be objects",
+        ]
+    );
+    assert_eq!(errors[0].details.type_name, Some("UUID".to_string()));
+}
+
+#[test]
+fn custom_scalar_validators_by_specified_by_url() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(
+        ValuesOfCorrectType::new().with_builtin_scalar_validators(),
+    ));
+    let errors = test_operation_with_schema(
+        r#"{
+          valid: events(after: "2007-12-03T10:15:30Z", id: "not checked")
+          invalid: events(after: "2007-12-03")
+        }"#,
+        CUSTOM_SCALARS_TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Expected value of type \"DateTime\", found \"2007-12-03\"; DateTime cannot represent an invalid date-time string \"2007-12-03\"."]
+    );
+}
+
+#[test]
+fn scalar_validators_change_the_fingerprint() {
+    assert_ne!(
+        ValuesOfCorrectType::new().fingerprint(),
+        ValuesOfCorrectType::new()
+            .with_builtin_scalar_validators()
+            .fingerprint()
+    );
+
+    let only_objects = |value: &Value| match value {
+        Value::Object(_) => Ok(()),
+        _ => Err("JSON filters must be objects".to_string()),
+    };
+
+    // Validators registered for the same scalar are told apart by their id.
+    assert_ne!(
+        ValuesOfCorrectType::new()
+            .with_scalar_validator("JSON", "json-object", only_objects)
+            .fingerprint(),
+        ValuesOfCorrectType::new()
+            .with_scalar_validator("JSON", "json-any", |_: &Value| Ok(()))
+            .fingerprint()
+    );
+    assert_eq!(
+        ValuesOfCorrectType::new()
+            .with_scalar_validator("JSON", "json-object", only_objects)
+            .fingerprint(),
+        ValuesOfCorrectType::new()
+            .with_scalar_validator("JSON", "json-object", only_objects)
+            .fingerprint()
+    );
+}
+
+#[test]
+fn one_of_input_object_with_exactly_one_field() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "query ($string: String!) {
+          complicatedArgs {
+            a: oneOfArgField(oneOfArg: { stringField: \"abc\" })
+            b: oneOfArgField(oneOfArg: { intField: 123 })
+            c: oneOfArgField(oneOfArg: { stringField: $string })
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn one_of_input_object_with_invalid_fields() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "{
+          complicatedArgs {
+            a: oneOfArgField(oneOfArg: { stringField: \"abc\", intField: 123 })
+            b: oneOfArgField(oneOfArg: {})
+            c: oneOfArgField(oneOfArg: { stringField: null })
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "OneOf Input Object \"OneOfInput\" must specify exactly one key.",
+            "OneOf Input Object \"OneOfInput\" must specify exactly one key.",
+            "Field \"OneOfInput.stringField\" must be non-null.",
+        ]
+    );
+    assert_eq!(
+        errors[2].details.field_name,
+        Some("stringField".to_string())
+    );
+}
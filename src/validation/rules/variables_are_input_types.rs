@@ -1,10 +1,11 @@
 use super::ValidationRule;
 use crate::ast::{
-    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
-    TypeDefinitionExtension, TypeExtension,
+    OperationVisitor, OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
+    TypeExtension,
 };
 use crate::validation::utils::ValidationError;
 use crate::validation::utils::ValidationErrorContext;
+use crate::validation::utils::ValidationErrorDetails;
 
 /// Variables are input types
 ///
@@ -34,7 +35,12 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesAreInputTypes
         {
             if !var_schema_type.is_input_type() {
                 user_context.report_error(ValidationError {
-                    error_code: self.error_code(),
+                    error_code: self.error_code().into(),
+                    details: ValidationErrorDetails {
+                        type_name: Some(variable_definition.var_type.inner_type().to_string()),
+                        variable_name: Some(variable_definition.name.to_string()),
+                        ..Default::default()
+                    },
                     message: format!(
                         "Variable \"${}\" cannot be non-input type \"{}\".",
                         variable_definition.name, variable_definition.var_type
@@ -51,17 +57,12 @@ impl ValidationRule for VariablesAreInputTypes {
         "VariablesAreInputTypes"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut VariablesAreInputTypes::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(VariablesAreInputTypes::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -1,12 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::parser::Pos;
 
 use crate::{
     ast::{
-        visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext,
-        SchemaDocumentExtension,
+        AstNodeWithName, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
+        TypeDefinitionExtension,
     },
     static_graphql::query::{Type, Value, VariableDefinition},
-    validation::utils::{ValidationError, ValidationErrorContext},
+    validation::utils::{ValidationError, ValidationErrorContext, ValidationErrorDetails},
 };
 
 use super::ValidationRule;
@@ -19,9 +21,19 @@ use super::ValidationRule;
 #[derive(Default)]
 pub struct VariablesInAllowedPosition<'a> {
     spreads: HashMap<Scope<'a>, HashSet<&'a str>>,
-    variable_usages: HashMap<Scope<'a>, Vec<(&'a str, &'a Type)>>,
+    variable_usages: HashMap<Scope<'a>, Vec<VariableUsage<'a>>>,
     variable_defs: HashMap<Scope<'a>, Vec<&'a VariableDefinition>>,
     current_scope: Option<Scope<'a>>,
+    /// The `@oneOf` input object types of the enclosing list and object values, if any.
+    one_of_stack: Vec<Option<String>>,
+}
+
+struct VariableUsage<'a> {
+    name: &'a str,
+    input_type: &'a Type,
+    position: Pos,
+    /// The `@oneOf` input object the variable is the value of a field of.
+    one_of_type_name: Option<String>,
 }
 
 impl<'a> VariablesInAllowedPosition<'a> {
@@ -31,6 +43,7 @@ impl<'a> VariablesInAllowedPosition<'a> {
             variable_usages: HashMap::new(),
             variable_defs: HashMap::new(),
             current_scope: None,
+            one_of_stack: Vec::new(),
         }
     }
 
@@ -49,8 +62,11 @@ impl<'a> VariablesInAllowedPosition<'a> {
         visited.insert(from.clone());
 
         if let Some(usages) = self.variable_usages.get(from) {
-            for (var_name, var_type) in usages {
-                if let Some(var_def) = var_defs.iter().find(|var_def| var_def.name == *var_name) {
+            for usage in usages {
+                let var_name = usage.name;
+                let var_type = usage.input_type;
+
+                if let Some(var_def) = var_defs.iter().find(|var_def| var_def.name == var_name) {
                     let expected_type = match (&var_def.default_value, &var_def.var_type) {
                         (Some(_), Type::ListType(inner)) => Type::NonNullType(inner.clone()),
                         (Some(default_value), Type::NamedType(_)) => {
@@ -65,15 +81,34 @@ impl<'a> VariablesInAllowedPosition<'a> {
 
                     if !visitor_context.schema.is_subtype(&expected_type, var_type) {
                         user_context.report_error(ValidationError {
-                          error_code: self.error_code(),
+                          error_code: self.error_code().into(),
+                          details: ValidationErrorDetails { variable_name: Some(var_name.to_string()), ..Default::default() },
                             message: format!("Variable \"${}\" of type \"{}\" used in position expecting type \"{}\".",
                                 var_name,
                                 expected_type,
                                 var_type,
                             ),
-                            locations: vec![var_def.position],
+                            locations: vec![var_def.position, usage.position],
                         });
                     }
+
+                    if let Some(one_of_type_name) = &usage.one_of_type_name {
+                        if !matches!(var_def.var_type, Type::NonNullType(_)) {
+                            user_context.report_error(ValidationError {
+                                error_code: self.error_code().into(),
+                                details: ValidationErrorDetails {
+                                    variable_name: Some(var_name.to_string()),
+                                    type_name: Some(one_of_type_name.clone()),
+                                    ..Default::default()
+                                },
+                                message: format!(
+                                    "Variable \"${}\" is of type \"{}\" but must be non-nullable to be used for OneOf Input Object \"{}\".",
+                                    var_name, var_def.var_type, one_of_type_name
+                                ),
+                                locations: vec![var_def.position, usage.position],
+                            });
+                        }
+                    }
                 }
             }
         }
@@ -168,16 +203,64 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesInAllowedPosi
         _: &mut ValidationErrorContext,
         variable_name: &'a str,
     ) {
-        if let (Some(scope), Some(input_type)) = (
+        if let (Some(scope), Some(input_type), Some(position)) = (
             &self.current_scope,
             visitor_context.current_input_type_literal(),
+            visitor_context.current_position(),
         ) {
             self.variable_usages
                 .entry(scope.clone())
                 .or_default()
-                .push((variable_name, input_type));
+                .push(VariableUsage {
+                    name: variable_name,
+                    input_type,
+                    position,
+                    one_of_type_name: self.one_of_stack.last().cloned().flatten(),
+                });
         }
     }
+
+    fn enter_list_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &Vec<Value>,
+    ) {
+        self.one_of_stack.push(None);
+    }
+
+    fn leave_list_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &Vec<Value>,
+    ) {
+        self.one_of_stack.pop();
+    }
+
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        let input_type = visitor_context.current_input_type();
+
+        self.one_of_stack.push(
+            input_type
+                .is_one_of_input_type()
+                .then(|| input_type.name().to_string()),
+        );
+    }
+
+    fn leave_object_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        self.one_of_stack.pop();
+    }
 }
 
 impl<'v> ValidationRule for VariablesInAllowedPosition<'v> {
@@ -185,17 +268,12 @@ impl<'v> ValidationRule for VariablesInAllowedPosition<'v> {
         "VariablesInAllowedPosition"
     }
 
-    fn validate(
-        &self,
-        ctx: &mut OperationVisitorContext,
-        error_collector: &mut ValidationErrorContext,
-    ) {
-        visit_document(
-            &mut VariablesInAllowedPosition::new(),
-            ctx.operation,
-            ctx,
-            error_collector,
-        );
+    fn visitor<'a>(&'a self) -> Box<dyn OperationVisitor<'a, ValidationErrorContext> + 'a> {
+        Box::new(VariablesInAllowedPosition::new())
+    }
+
+    fn fingerprint(&self) -> String {
+        self.error_code().to_string()
     }
 }
 
@@ -681,3 +759,85 @@ fn boolean_to_boolean_non_null_with_default_value() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 0);
 }
+
+#[test]
+fn reports_definition_and_usage_locations() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(VariablesInAllowedPosition::new()));
+    let errors = test_operation_with_schema(
+        "query Query($intArg: Int) {
+          complicatedArgs {
+            nonNullIntArgField(nonNullIntArg: $intArg)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 1);
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos {
+                line: 1,
+                column: 13
+            },
+            Pos {
+                line: 3,
+                column: 13
+            }
+        ]
+    );
+}
+
+#[test]
+fn non_null_variables_in_one_of_input_objects() {
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(VariablesInAllowedPosition::new()));
+    let errors = test_operation_with_schema(
+        "query ($string: String!, $strings: [String]) {
+          complicatedArgs {
+            oneOfArgField(oneOfArg: { stringField: $string })
+            stringListArgField(stringListArg: $strings)
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn nullable_variable_in_one_of_input_object() {
+    use crate::parser::Pos;
+    use crate::validation::test_utils::*;
+
+    let plan = create_plan_from_rule(Box::new(VariablesInAllowedPosition::new()));
+    let errors = test_operation_with_schema(
+        "query ($string: String) {
+          complicatedArgs {
+            oneOfArgField(oneOfArg: { stringField: $string })
+          }
+        }",
+        TEST_SCHEMA,
+        &plan,
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Variable \"$string\" is of type \"String\" but must be non-nullable to be used for OneOf Input Object \"OneOfInput\"."]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![
+            Pos { line: 1, column: 8 },
+            Pos {
+                line: 3,
+                column: 13
+            }
+        ]
+    );
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use crate::static_graphql::query::Value;
+
+/// Validates the literals given to a custom scalar, see `ValuesOfCorrectType::with_scalar_validator`.
+pub trait ScalarValidator: Send + Sync {
+    /// Returns the reason why `value` is not a valid literal for the scalar.
+    fn validate(&self, value: &Value) -> Result<(), String>;
+}
+
+impl<F> ScalarValidator for F
+where
+    F: Fn(&Value) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        self(value)
+    }
+}
+
+pub const DATE_TIME_SPECIFIED_BY_URL: &str = "https://scalars.graphql.org/andimarek/date-time";
+pub const UUID_SPECIFIED_BY_URL: &str = "https://tools.ietf.org/html/rfc4122";
+pub const URL_SPECIFIED_BY_URL: &str = "https://url.spec.whatwg.org/";
+
+/// The validators shipped with this crate, keyed by the `@specifiedBy` URL of the scalars they
+/// validate.
+pub fn specified_by_validators() -> Vec<(&'static str, Arc<dyn ScalarValidator>)> {
+    vec![
+        (DATE_TIME_SPECIFIED_BY_URL, Arc::new(DateTimeValidator)),
+        (UUID_SPECIFIED_BY_URL, Arc::new(UuidValidator)),
+        (URL_SPECIFIED_BY_URL, Arc::new(UrlValidator)),
+    ]
+}
+
+fn expect_string<'v>(value: &'v Value, scalar_name: &str) -> Result<&'v str, String> {
+    match value {
+        Value::String(value) => Ok(value),
+        value => Err(format!(
+            "{} cannot represent a non string value: {}",
+            scalar_name, value
+        )),
+    }
+}
+
+/// A date-time string with an offset, as defined by RFC 3339: `2007-12-03T10:15:30.123Z`.
+pub struct DateTimeValidator;
+
+impl DateTimeValidator {
+    fn is_valid(value: &str) -> bool {
+        let bytes = value.as_bytes();
+
+        let number = |range: std::ops::Range<usize>| -> Option<u32> {
+            let digits = bytes.get(range)?;
+
+            if !digits.iter().all(u8::is_ascii_digit) {
+                return None;
+            }
+
+            std::str::from_utf8(digits).ok()?.parse().ok()
+        };
+        let byte_is = |index: usize, expected: &[u8]| {
+            bytes.get(index).is_some_and(|byte| expected.contains(byte))
+        };
+
+        let (Some(year), Some(month), Some(day)) = (number(0..4), number(5..7), number(8..10))
+        else {
+            return false;
+        };
+        let (Some(hour), Some(minute), Some(second)) =
+            (number(11..13), number(14..16), number(17..19))
+        else {
+            return false;
+        };
+
+        if !(byte_is(4, b"-")
+            && byte_is(7, b"-")
+            && byte_is(10, b"Tt")
+            && byte_is(13, b":")
+            && byte_is(16, b":"))
+        {
+            return false;
+        }
+
+        let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year => 29,
+            2 => 28,
+            _ => return false,
+        };
+
+        if day == 0 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+            return false;
+        }
+
+        let mut offset = 19;
+
+        if byte_is(offset, b".") {
+            offset += 1;
+            let fraction_start = offset;
+
+            while bytes.get(offset).is_some_and(u8::is_ascii_digit) {
+                offset += 1;
+            }
+
+            if offset == fraction_start {
+                return false;
+            }
+        }
+
+        if byte_is(offset, b"Zz") {
+            return bytes.len() == offset + 1;
+        }
+
+        byte_is(offset, b"+-")
+            && byte_is(offset + 3, b":")
+            && bytes.len() == offset + 6
+            && number(offset + 1..offset + 3).is_some_and(|hours| hours <= 23)
+            && number(offset + 4..offset + 6).is_some_and(|minutes| minutes <= 59)
+    }
+}
+
+impl ScalarValidator for DateTimeValidator {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = expect_string(value, "DateTime")?;
+
+        if Self::is_valid(value) {
+            Ok(())
+        } else {
+            Err(format!(
+                "DateTime cannot represent an invalid date-time string \"{}\".",
+                value
+            ))
+        }
+    }
+}
+
+/// A UUID in its canonical, hyphenated form: `123e4567-e89b-12d3-a456-426614174000`.
+pub struct UuidValidator;
+
+impl ScalarValidator for UuidValidator {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = expect_string(value, "UUID")?;
+        let is_valid = value.len() == 36
+            && value.char_indices().all(|(index, c)| match index {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            });
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(format!(
+                "UUID cannot represent an invalid UUID \"{}\".",
+                value
+            ))
+        }
+    }
+}
+
+/// An absolute URL: a scheme followed by a non-empty, whitespace free, remainder.
+pub struct UrlValidator;
+
+impl ScalarValidator for UrlValidator {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = expect_string(value, "URL")?;
+        let is_valid = match value.split_once(':') {
+            Some((scheme, rest)) => {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                    && !rest.is_empty()
+                    && !rest.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(format!(
+                "URL cannot represent an invalid URL \"{}\".",
+                value
+            ))
+        }
+    }
+}
+
+#[test]
+fn date_time_validator() {
+    let validate = |value: &str| DateTimeValidator.validate(&Value::String(value.to_string()));
+
+    assert!(validate("2007-12-03T10:15:30Z").is_ok());
+    assert!(validate("2007-12-03T10:15:30.123456+01:00").is_ok());
+    assert!(validate("2020-02-29t00:00:00z").is_ok());
+    assert!(validate("2021-02-29T00:00:00Z").is_err());
+    assert!(validate("2007-12-03T24:15:30Z").is_err());
+    assert!(validate("2007-12-03T10:15:30").is_err());
+    assert!(validate("2007-12-03T10:15:30.Z").is_err());
+    assert!(validate("2007-12-03").is_err());
+    assert_eq!(
+        DateTimeValidator.validate(&Value::Int(10.into())),
+        Err("DateTime cannot represent a non string value: 10".to_string())
+    );
+}
+
+#[test]
+fn uuid_validator() {
+    let validate = |value: &str| UuidValidator.validate(&Value::String(value.to_string()));
+
+    assert!(validate("123e4567-e89b-12d3-a456-426614174000").is_ok());
+    assert!(validate("123E4567-E89B-12D3-A456-426614174000").is_ok());
+    assert!(validate("123e4567e89b12d3a456426614174000").is_err());
+    assert!(validate("123e4567-e89b-12d3-a456-42661417400g").is_err());
+}
+
+#[test]
+fn url_validator() {
+    let validate = |value: &str| UrlValidator.validate(&Value::String(value.to_string()));
+
+    assert!(validate("https://the-guild.dev/graphql/hive").is_ok());
+    assert!(validate("mailto:someone@example.com").is_ok());
+    assert!(validate("the-guild.dev").is_err());
+    assert!(validate("https://the guild.dev").is_err());
+    assert!(validate("1http://example.com").is_err());
+}
@@ -172,6 +172,10 @@ input ComplexInput {
   booleanField: Boolean
   stringListField: [String]
 }
+input OneOfInput @oneOf {
+  stringField: String
+  intField: Int
+}
 type ComplicatedArgs {
   # TODO List
   # TODO Coercion
@@ -186,6 +190,7 @@ type ComplicatedArgs {
   stringListArgField(stringListArg: [String]): String
   stringListNonNullArgField(stringListNonNullArg: [String!]): String
   complexArgField(complexArg: ComplexInput): String
+  oneOfArgField(oneOfArg: OneOfInput): String
   multipleReqs(req1: Int!, req2: Int!): String
   nonNullFieldWithDefault(arg: Int! = 0): String
   multipleOpts(opt1: Int = 0, opt2: Int = 0): String
@@ -228,12 +233,45 @@ directive @testDirective on FIELD | FRAGMENT_DEFINITION
 directive @repeatable repeatable on FIELD | FRAGMENT_DEFINITION
 ";
 
+#[cfg(test)]
+pub static DEFER_STREAM_TEST_SCHEMA: &str = "
+directive @defer(if: Boolean! = true, label: String) on FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @stream(if: Boolean! = true, label: String, initialCount: Int = 0) on FIELD
+
+schema {
+  query: Query
+  mutation: Mutation
+  subscription: Subscription
+}
+
+type Message {
+  body: String
+  sender: String
+}
+
+type Query {
+  message: Message
+  messages: [Message]
+  nonNullMessages: [Message!]!
+}
+
+type Mutation {
+  mutationField: String
+  mutationListField: [String]
+}
+
+type Subscription {
+  subscriptionField: Message
+  subscriptionListField: [Message]
+}
+";
+
 #[cfg(test)]
 pub fn create_plan_from_rule(rule: Box<dyn ValidationRule>) -> ValidationPlan {
     let mut rules = Vec::new();
     rules.push(rule);
 
-    ValidationPlan { rules }
+    ValidationPlan::from(rules)
 }
 
 #[cfg(test)]
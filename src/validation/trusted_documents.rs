@@ -0,0 +1,444 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use super::utils::ValidationError;
+use super::validate::{validate_with_prepared_schema, ValidationPlan};
+use crate::ast::prepared_schema::fingerprint;
+use crate::ast::PreparedSchema;
+use crate::static_graphql::query;
+
+/// An operation document allowed by a manifest.
+#[derive(Debug, Clone)]
+pub struct TrustedDocument {
+    /// The id given by the manifest. Opaque: it's not derived from, nor checked against, the
+    /// document.
+    pub id: String,
+    /// The name of the operation, when the manifest gives one.
+    pub name: Option<String>,
+    /// The document as written in the manifest.
+    pub body: String,
+    pub document: query::Document,
+}
+
+/// A manifest entry that no longer validates against a schema.
+#[derive(Debug, Clone)]
+pub struct BrokenTrustedDocument {
+    pub id: String,
+    pub name: Option<String>,
+    pub errors: Vec<ValidationError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrustedDocumentsError {
+    /// The manifest is not valid JSON, or not in a supported format.
+    InvalidManifest(String),
+    /// A manifest entry is not a valid GraphQL document.
+    InvalidDocument { id: String, reason: String },
+    /// No document is stored for this id.
+    UnknownId(String),
+    /// The document is not in the manifest.
+    UnknownDocument,
+}
+
+impl Display for TrustedDocumentsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrustedDocumentsError::InvalidManifest(reason) => {
+                write!(f, "Invalid trusted documents manifest: {}", reason)
+            }
+            TrustedDocumentsError::InvalidDocument { id, reason } => {
+                write!(f, "Invalid trusted document \"{}\": {}", id, reason)
+            }
+            TrustedDocumentsError::UnknownId(id) => {
+                write!(f, "Unknown trusted document id \"{}\".", id)
+            }
+            TrustedDocumentsError::UnknownDocument => {
+                write!(f, "Only trusted documents are allowed.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TrustedDocumentsError {}
+
+/// The format of manifests generated by `@apollo/generate-persisted-query-manifest`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApolloManifest {
+    format: String,
+    version: u64,
+    operations: Vec<ApolloManifestOperation>,
+}
+
+#[derive(Deserialize)]
+struct ApolloManifestOperation {
+    id: String,
+    body: String,
+    name: Option<String>,
+}
+
+const APOLLO_MANIFEST_FORMAT: &str = "apollo-persisted-query-manifest";
+
+/// Persisted documents, or trusted documents: the operations a server accepts, by id.
+///
+/// Documents are compared in their normalized form, the document printed without comments and
+/// with canonical formatting, so a client can send the full text of a document instead of its
+/// id.
+///
+/// Ids are opaque keys: even when a manifest uses hashes of the documents as ids (like the
+/// SHA-256 ids of Apollo manifests), they are not recomputed nor checked against the documents.
+/// Only load manifests from a trusted source, a manifest can map any id to any document.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedDocuments {
+    documents: BTreeMap<String, TrustedDocument>,
+    /// The ids of each normalized document: a manifest can store a document under many ids.
+    ids_by_normalized_document: HashMap<String, BTreeSet<String>>,
+    fingerprint: OnceLock<u64>,
+}
+
+impl TrustedDocuments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a manifest in the Apollo format:
+    /// `{ "format": "apollo-persisted-query-manifest", "version": 1, "operations": [{ "id", "name", "type", "body" }] }`.
+    pub fn from_apollo_manifest(manifest: &str) -> Result<Self, TrustedDocumentsError> {
+        let manifest: ApolloManifest = serde_json::from_str(manifest)
+            .map_err(|e| TrustedDocumentsError::InvalidManifest(e.to_string()))?;
+
+        if manifest.format != APOLLO_MANIFEST_FORMAT || manifest.version != 1 {
+            return Err(TrustedDocumentsError::InvalidManifest(format!(
+                "unsupported format \"{}\" version {}",
+                manifest.format, manifest.version
+            )));
+        }
+
+        let mut documents = Self::new();
+
+        for operation in manifest.operations {
+            documents.insert(operation.id, operation.name, operation.body)?;
+        }
+
+        documents.fingerprint();
+
+        Ok(documents)
+    }
+
+    /// Loads a manifest in the Relay format, generated with `persistConfig`: `{ "<id>": "<document>" }`.
+    pub fn from_relay_manifest(manifest: &str) -> Result<Self, TrustedDocumentsError> {
+        let manifest: BTreeMap<String, String> = serde_json::from_str(manifest)
+            .map_err(|e| TrustedDocumentsError::InvalidManifest(e.to_string()))?;
+
+        let mut documents = Self::new();
+
+        for (id, body) in manifest {
+            documents.insert(id, None, body)?;
+        }
+
+        documents.fingerprint();
+
+        Ok(documents)
+    }
+
+    /// Loads a manifest in the Apollo or the Relay format.
+    pub fn from_manifest(manifest: &str) -> Result<Self, TrustedDocumentsError> {
+        let json: JsonValue = serde_json::from_str(manifest)
+            .map_err(|e| TrustedDocumentsError::InvalidManifest(e.to_string()))?;
+
+        if json.get("format").is_some() {
+            Self::from_apollo_manifest(manifest)
+        } else {
+            Self::from_relay_manifest(manifest)
+        }
+    }
+
+    /// Adds a document, replacing the document stored with the same id.
+    pub fn insert(
+        &mut self,
+        id: String,
+        name: Option<String>,
+        body: String,
+    ) -> Result<(), TrustedDocumentsError> {
+        let document = crate::parser::parse_query::<String>(&body)
+            .map_err(|e| TrustedDocumentsError::InvalidDocument {
+                id: id.clone(),
+                reason: e.to_string(),
+            })?
+            .into_static();
+
+        if let Some(replaced) = self.documents.get(&id) {
+            let normalized = replaced.document.to_string();
+
+            if let Some(ids) = self.ids_by_normalized_document.get_mut(&normalized) {
+                ids.remove(&id);
+
+                if ids.is_empty() {
+                    self.ids_by_normalized_document.remove(&normalized);
+                }
+            }
+        }
+
+        self.fingerprint = OnceLock::new();
+        self.ids_by_normalized_document
+            .entry(document.to_string())
+            .or_default()
+            .insert(id.clone());
+        self.documents.insert(
+            id.clone(),
+            TrustedDocument {
+                id,
+                name,
+                body,
+                document,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// The documents, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = &TrustedDocument> {
+        self.documents.values()
+    }
+
+    /// Resolves the id sent by a client.
+    pub fn get(&self, id: &str) -> Result<&TrustedDocument, TrustedDocumentsError> {
+        self.documents
+            .get(id)
+            .ok_or_else(|| TrustedDocumentsError::UnknownId(id.to_string()))
+    }
+
+    /// Looks up a parsed document by its normalized form. When the document is stored under
+    /// many ids, the first id wins.
+    pub fn find(&self, document: &query::Document) -> Option<&TrustedDocument> {
+        self.find_normalized(&document.to_string())
+    }
+
+    /// Checks that the text of a document sent by a client is allowed.
+    pub fn find_by_text(&self, text: &str) -> Result<&TrustedDocument, TrustedDocumentsError> {
+        let document = crate::parser::parse_query::<String>(text)
+            .map_err(|_| TrustedDocumentsError::UnknownDocument)?;
+
+        self.find_normalized(&document.to_string())
+            .ok_or(TrustedDocumentsError::UnknownDocument)
+    }
+
+    fn find_normalized(&self, normalized: &str) -> Option<&TrustedDocument> {
+        self.ids_by_normalized_document
+            .get(normalized)
+            .and_then(|ids| ids.first())
+            .and_then(|id| self.documents.get(id))
+    }
+
+    /// A hash of the normalized documents and their ids, for cache keys. Computed when a
+    /// manifest is loaded, and again after `insert`.
+    pub fn fingerprint(&self) -> u64 {
+        *self.fingerprint.get_or_init(|| {
+            let mut normalized = String::new();
+
+            for (id, trusted_document) in &self.documents {
+                normalized.push_str(id);
+                normalized.push('\n');
+                normalized.push_str(&trusted_document.document.to_string());
+            }
+
+            fingerprint(&normalized)
+        })
+    }
+
+    /// Validates every document against a schema, to find the documents a schema change breaks.
+    pub fn validate_all(
+        &self,
+        schema: &PreparedSchema,
+        validation_plan: &ValidationPlan,
+    ) -> Vec<BrokenTrustedDocument> {
+        self.documents
+            .values()
+            .filter_map(|trusted_document| {
+                let errors = validate_with_prepared_schema(
+                    schema,
+                    &trusted_document.document,
+                    validation_plan,
+                );
+
+                if errors.is_empty() {
+                    return None;
+                }
+
+                Some(BrokenTrustedDocument {
+                    id: trusted_document.id.clone(),
+                    name: trusted_document.name.clone(),
+                    errors,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+static APOLLO_MANIFEST: &str = r#"{
+  "format": "apollo-persisted-query-manifest",
+  "version": 1,
+  "operations": [
+    {
+      "id": "a1",
+      "name": "DogName",
+      "type": "query",
+      "body": "query DogName { dog { name } }"
+    },
+    {
+      "id": "b2",
+      "name": "CatFur",
+      "type": "query",
+      "body": "query CatFur { cat { furColor } }"
+    }
+  ]
+}"#;
+
+#[test]
+fn loads_apollo_and_relay_manifests() {
+    let documents = TrustedDocuments::from_manifest(APOLLO_MANIFEST).unwrap();
+
+    assert_eq!(documents.len(), 2);
+    assert_eq!(
+        documents.get("a1").unwrap().name,
+        Some("DogName".to_string())
+    );
+    assert_eq!(
+        documents.get("c3").err(),
+        Some(TrustedDocumentsError::UnknownId("c3".to_string()))
+    );
+
+    let documents =
+        TrustedDocuments::from_manifest(r#"{ "x9": "query DogName { dog { name } }" }"#).unwrap();
+
+    assert_eq!(
+        documents.iter().map(|d| d.id.as_str()).collect::<Vec<_>>(),
+        vec!["x9"]
+    );
+    assert_eq!(documents.get("x9").unwrap().name, None);
+}
+
+#[test]
+fn rejects_invalid_manifests() {
+    assert!(matches!(
+        TrustedDocuments::from_manifest(r#"{ "format": "other", "version": 1, "operations": [] }"#),
+        Err(TrustedDocumentsError::InvalidManifest(_))
+    ));
+    assert!(matches!(
+        TrustedDocuments::from_manifest("[]"),
+        Err(TrustedDocumentsError::InvalidManifest(_))
+    ));
+    assert!(matches!(
+        TrustedDocuments::from_manifest(r#"{ "x9": "query {" }"#),
+        Err(TrustedDocumentsError::InvalidDocument { ref id, .. }) if id == "x9"
+    ));
+}
+
+#[test]
+fn finds_documents_by_normalized_text() {
+    let documents = TrustedDocuments::from_manifest(APOLLO_MANIFEST).unwrap();
+
+    assert_eq!(
+        documents
+            .find_by_text(
+                "# fetches the name
+                query   DogName {
+                  dog { name }
+                }"
+            )
+            .unwrap()
+            .id,
+        "a1"
+    );
+    assert_eq!(
+        documents
+            .find_by_text("query DogName { dog { name nickname } }")
+            .err(),
+        Some(TrustedDocumentsError::UnknownDocument)
+    );
+}
+
+#[test]
+fn replacing_an_id_keeps_the_other_ids_of_its_document() {
+    let mut documents = TrustedDocuments::new();
+    documents
+        .insert("a".to_string(), None, "{ dog { name } }".to_string())
+        .unwrap();
+    documents
+        .insert("b".to_string(), None, "{ dog { name } }".to_string())
+        .unwrap();
+    documents
+        .insert("b".to_string(), None, "{ dog { nickname } }".to_string())
+        .unwrap();
+
+    assert_eq!(documents.find_by_text("{ dog { name } }").unwrap().id, "a");
+    assert_eq!(
+        documents.find_by_text("{ dog { nickname } }").unwrap().id,
+        "b"
+    );
+
+    documents
+        .insert("a".to_string(), None, "{ dog { barkVolume } }".to_string())
+        .unwrap();
+
+    assert_eq!(
+        documents.find_by_text("{ dog { name } }").err(),
+        Some(TrustedDocumentsError::UnknownDocument)
+    );
+}
+
+#[test]
+fn fingerprint_follows_inserted_documents() {
+    let mut documents = TrustedDocuments::from_manifest(APOLLO_MANIFEST).unwrap();
+    let loaded = documents.fingerprint();
+
+    assert_eq!(
+        TrustedDocuments::from_manifest(APOLLO_MANIFEST)
+            .unwrap()
+            .fingerprint(),
+        loaded
+    );
+
+    documents
+        .insert("a1".to_string(), None, "{ dog { nickname } }".to_string())
+        .unwrap();
+
+    assert_ne!(documents.fingerprint(), loaded);
+}
+
+#[test]
+fn reports_documents_broken_by_a_schema() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    let documents = TrustedDocuments::from_manifest(APOLLO_MANIFEST).unwrap();
+    let schema_ast =
+        crate::parser::parse_schema::<String>(&TEST_SCHEMA.replace("  furColor: FurColor\n", ""))
+            .unwrap()
+            .into_static();
+    let schema = PreparedSchema::from_document(schema_ast);
+
+    let broken = documents.validate_all(&schema, &default_rules_validation_plan());
+
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].id, "b2");
+    assert_eq!(broken[0].name, Some("CatFur".to_string()));
+    assert_eq!(
+        broken[0].errors[0].message,
+        "Cannot query field \"furColor\" on type \"Cat\"."
+    );
+}
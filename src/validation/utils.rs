@@ -1,8 +1,10 @@
 use crate::parser::Pos;
+use crate::static_graphql::query::{Field, FragmentDefinition, Selection, SelectionSet};
 use serde::ser::*;
 use serde::{Serialize, Serializer};
-use serde_with::{serde_as, SerializeAs};
-use std::fmt::Debug;
+use serde_with::{ser::SerializeAsWrap, SerializeAs};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display};
 
 #[derive(Debug, Default)]
 pub struct ValidationErrorContext {
@@ -19,7 +21,8 @@ impl ValidationErrorContext {
     }
 }
 
-struct PositionDef;
+/// Serializes a `Pos` as a GraphQL error location: `{"line": 1, "column": 2}`.
+pub(crate) struct PositionDef;
 
 impl SerializeAs<Pos> for PositionDef {
     fn serialize_as<S>(value: &Pos, serializer: S) -> Result<S::Ok, S::Error>
@@ -33,14 +36,461 @@ impl SerializeAs<Pos> for PositionDef {
     }
 }
 
-#[serde_as]
-#[derive(Serialize, Debug, Clone)]
+/// Identifies the validation rule that reported an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationRuleCode {
+    UniqueOperationNames,
+    LoneAnonymousOperation,
+    KnownOperationTypes,
+    SingleFieldSubscriptions,
+    KnownTypeNames,
+    FragmentsOnCompositeTypes,
+    VariablesAreInputTypes,
+    LeafFieldSelections,
+    FieldsOnCorrectType,
+    UniqueFragmentNames,
+    KnownFragmentNames,
+    NoUnusedFragments,
+    OverlappingFieldsCanBeMerged,
+    NoFragmentsCycle,
+    PossibleFragmentSpreads,
+    NoUnusedVariables,
+    NoUndefinedVariables,
+    KnownArgumentNames,
+    UniqueArgumentNames,
+    UniqueVariableNames,
+    ProvidedRequiredArguments,
+    KnownDirectives,
+    VariablesInAllowedPosition,
+    ValuesOfCorrectType,
+    UniqueDirectivesPerLocation,
+    DeferStreamDirectiveLabel,
+    DeferStreamDirectiveOnRootField,
+    DeferStreamDirectiveOnValidOperations,
+    StreamDirectiveOnListField,
+    MaxAliases,
+    MaxCost,
+    MaxDepth,
+    MaxDirectives,
+    MaxFields,
+    MaxRootFields,
+    NoDeprecated,
+    NoSchemaIntrospection,
+    OnlyTrustedDocuments,
+    /// Not a rule: validation stopped early, after reaching a limit of `ValidationOptions`.
+    ValidationAborted,
+    /// A rule implemented outside of this crate, identified by its `error_code`.
+    Custom(&'static str),
+}
+
+impl ValidationRuleCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationRuleCode::UniqueOperationNames => "UniqueOperationNames",
+            ValidationRuleCode::LoneAnonymousOperation => "LoneAnonymousOperation",
+            ValidationRuleCode::KnownOperationTypes => "KnownOperationTypes",
+            ValidationRuleCode::SingleFieldSubscriptions => "SingleFieldSubscriptions",
+            ValidationRuleCode::KnownTypeNames => "KnownTypeNames",
+            ValidationRuleCode::FragmentsOnCompositeTypes => "FragmentsOnCompositeTypes",
+            ValidationRuleCode::VariablesAreInputTypes => "VariablesAreInputTypes",
+            ValidationRuleCode::LeafFieldSelections => "LeafFieldSelections",
+            ValidationRuleCode::FieldsOnCorrectType => "FieldsOnCorrectType",
+            ValidationRuleCode::UniqueFragmentNames => "UniqueFragmentNames",
+            ValidationRuleCode::KnownFragmentNames => "KnownFragmentNames",
+            ValidationRuleCode::NoUnusedFragments => "NoUnusedFragments",
+            ValidationRuleCode::OverlappingFieldsCanBeMerged => "OverlappingFieldsCanBeMerged",
+            ValidationRuleCode::NoFragmentsCycle => "NoFragmentsCycle",
+            ValidationRuleCode::PossibleFragmentSpreads => "PossibleFragmentSpreads",
+            ValidationRuleCode::NoUnusedVariables => "NoUnusedVariables",
+            ValidationRuleCode::NoUndefinedVariables => "NoUndefinedVariables",
+            ValidationRuleCode::KnownArgumentNames => "KnownArgumentNames",
+            ValidationRuleCode::UniqueArgumentNames => "UniqueArgumentNames",
+            ValidationRuleCode::UniqueVariableNames => "UniqueVariableNames",
+            ValidationRuleCode::ProvidedRequiredArguments => "ProvidedRequiredArguments",
+            ValidationRuleCode::KnownDirectives => "KnownDirectives",
+            ValidationRuleCode::VariablesInAllowedPosition => "VariablesInAllowedPosition",
+            ValidationRuleCode::ValuesOfCorrectType => "ValuesOfCorrectType",
+            ValidationRuleCode::UniqueDirectivesPerLocation => "UniqueDirectivesPerLocation",
+            ValidationRuleCode::DeferStreamDirectiveLabel => "DeferStreamDirectiveLabel",
+            ValidationRuleCode::DeferStreamDirectiveOnRootField => {
+                "DeferStreamDirectiveOnRootField"
+            }
+            ValidationRuleCode::DeferStreamDirectiveOnValidOperations => {
+                "DeferStreamDirectiveOnValidOperations"
+            }
+            ValidationRuleCode::StreamDirectiveOnListField => "StreamDirectiveOnListField",
+            ValidationRuleCode::MaxAliases => "MaxAliases",
+            ValidationRuleCode::MaxCost => "MaxCost",
+            ValidationRuleCode::MaxDepth => "MaxDepth",
+            ValidationRuleCode::MaxDirectives => "MaxDirectives",
+            ValidationRuleCode::MaxFields => "MaxFields",
+            ValidationRuleCode::MaxRootFields => "MaxRootFields",
+            ValidationRuleCode::NoDeprecated => "NoDeprecated",
+            ValidationRuleCode::NoSchemaIntrospection => "NoSchemaIntrospection",
+            ValidationRuleCode::OnlyTrustedDocuments => "OnlyTrustedDocuments",
+            ValidationRuleCode::ValidationAborted => "ValidationAborted",
+            ValidationRuleCode::Custom(code) => code,
+        }
+    }
+}
+
+impl From<&'static str> for ValidationRuleCode {
+    fn from(code: &'static str) -> Self {
+        match code {
+            "UniqueOperationNames" => ValidationRuleCode::UniqueOperationNames,
+            "LoneAnonymousOperation" => ValidationRuleCode::LoneAnonymousOperation,
+            "KnownOperationTypes" => ValidationRuleCode::KnownOperationTypes,
+            "SingleFieldSubscriptions" => ValidationRuleCode::SingleFieldSubscriptions,
+            "KnownTypeNames" => ValidationRuleCode::KnownTypeNames,
+            "FragmentsOnCompositeTypes" => ValidationRuleCode::FragmentsOnCompositeTypes,
+            "VariablesAreInputTypes" => ValidationRuleCode::VariablesAreInputTypes,
+            "LeafFieldSelections" => ValidationRuleCode::LeafFieldSelections,
+            "FieldsOnCorrectType" => ValidationRuleCode::FieldsOnCorrectType,
+            "UniqueFragmentNames" => ValidationRuleCode::UniqueFragmentNames,
+            "KnownFragmentNames" => ValidationRuleCode::KnownFragmentNames,
+            "NoUnusedFragments" => ValidationRuleCode::NoUnusedFragments,
+            "OverlappingFieldsCanBeMerged" => ValidationRuleCode::OverlappingFieldsCanBeMerged,
+            "NoFragmentsCycle" => ValidationRuleCode::NoFragmentsCycle,
+            "PossibleFragmentSpreads" => ValidationRuleCode::PossibleFragmentSpreads,
+            "NoUnusedVariables" => ValidationRuleCode::NoUnusedVariables,
+            "NoUndefinedVariables" => ValidationRuleCode::NoUndefinedVariables,
+            "KnownArgumentNames" => ValidationRuleCode::KnownArgumentNames,
+            "UniqueArgumentNames" => ValidationRuleCode::UniqueArgumentNames,
+            "UniqueVariableNames" => ValidationRuleCode::UniqueVariableNames,
+            "ProvidedRequiredArguments" => ValidationRuleCode::ProvidedRequiredArguments,
+            "KnownDirectives" => ValidationRuleCode::KnownDirectives,
+            "VariablesInAllowedPosition" => ValidationRuleCode::VariablesInAllowedPosition,
+            "ValuesOfCorrectType" => ValidationRuleCode::ValuesOfCorrectType,
+            "UniqueDirectivesPerLocation" => ValidationRuleCode::UniqueDirectivesPerLocation,
+            "DeferStreamDirectiveLabel" => ValidationRuleCode::DeferStreamDirectiveLabel,
+            "DeferStreamDirectiveOnRootField" => {
+                ValidationRuleCode::DeferStreamDirectiveOnRootField
+            }
+            "DeferStreamDirectiveOnValidOperations" => {
+                ValidationRuleCode::DeferStreamDirectiveOnValidOperations
+            }
+            "StreamDirectiveOnListField" => ValidationRuleCode::StreamDirectiveOnListField,
+            "MaxAliases" => ValidationRuleCode::MaxAliases,
+            "MaxCost" => ValidationRuleCode::MaxCost,
+            "MaxDepth" => ValidationRuleCode::MaxDepth,
+            "MaxDirectives" => ValidationRuleCode::MaxDirectives,
+            "MaxFields" => ValidationRuleCode::MaxFields,
+            "MaxRootFields" => ValidationRuleCode::MaxRootFields,
+            "NoDeprecated" => ValidationRuleCode::NoDeprecated,
+            "NoSchemaIntrospection" => ValidationRuleCode::NoSchemaIntrospection,
+            "OnlyTrustedDocuments" => ValidationRuleCode::OnlyTrustedDocuments,
+            "ValidationAborted" => ValidationRuleCode::ValidationAborted,
+            code => ValidationRuleCode::Custom(code),
+        }
+    }
+}
+
+impl PartialEq<&str> for ValidationRuleCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for ValidationRuleCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Structured data about the nodes involved in a validation error, so consumers don't need to
+/// parse messages. Only the entries relevant to the reporting rule are set.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationErrorDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragment_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directive_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable_name: Option<String>,
+    /// Response keys of the fields leading to the error, from the operation root.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<String>,
+    /// Known names close to the unknown one, best match first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+/// `extensions.code` of every validation error, as in GraphQL servers.
+pub const GRAPHQL_VALIDATION_FAILED: &str = "GRAPHQL_VALIDATION_FAILED";
+
+#[derive(Debug, Clone)]
 pub struct ValidationError {
-    #[serde_as(as = "Vec<PositionDef>")]
     pub locations: Vec<Pos>,
     pub message: String,
-    #[serde(skip_serializing)]
-    pub error_code: &'static str,
+    pub error_code: ValidationRuleCode,
+    pub details: ValidationErrorDetails,
+}
+
+/// Serialized as a GraphQL response error, with the rule and its details in `extensions`:
+/// `{"message": ..., "locations": [...], "extensions": {"code": "GRAPHQL_VALIDATION_FAILED", "rule": ...}}`
+impl Serialize for ValidationError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Extensions<'a> {
+            code: &'static str,
+            rule: &'static str,
+            #[serde(flatten)]
+            details: &'a ValidationErrorDetails,
+        }
+
+        let mut s = serializer.serialize_map(Some(3))?;
+        s.serialize_entry("message", &self.message)?;
+        s.serialize_entry(
+            "locations",
+            &SerializeAsWrap::<Vec<Pos>, Vec<PositionDef>>::new(&self.locations),
+        )?;
+        s.serialize_entry(
+            "extensions",
+            &Extensions {
+                code: GRAPHQL_VALIDATION_FAILED,
+                rule: self.error_code.as_str(),
+                details: &self.details,
+            },
+        )?;
+        s.end()
+    }
+}
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Returns the options close enough to `input` to be suggested, closest first, ranked by
+/// lexical distance the same way as graphql-js.
+pub fn suggestion_list<I, S>(input: &str, options: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let threshold = input.chars().count() * 2 / 5 + 1;
+    let mut suggestions = options
+        .into_iter()
+        .filter_map(|option| {
+            let option = option.as_ref();
+            lexical_distance(input, option, threshold)
+                .map(|distance| (distance, option.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    suggestions.sort();
+    suggestions.dedup();
+    suggestions.into_iter().map(|(_, option)| option).collect()
+}
+
+/// Case-insensitive Damerau-Levenshtein distance (optimal string alignment), where a difference
+/// in case only counts as a distance of 1. Returns `None` when the distance exceeds `threshold`.
+fn lexical_distance(input: &str, option: &str, threshold: usize) -> Option<usize> {
+    if input == option {
+        return Some(0);
+    }
+
+    let input = input.to_lowercase().chars().collect::<Vec<_>>();
+    let option = option.to_lowercase().chars().collect::<Vec<_>>();
+
+    if input == option {
+        return Some(1);
+    }
+
+    let (a, b) = if option.len() < input.len() {
+        (&input, &option)
+    } else {
+        (&option, &input)
+    };
+
+    if a.len() - b.len() > threshold {
+        return None;
+    }
+
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        rows[i][0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut current = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current = current.min(rows[i - 2][j - 2] + 1);
+            }
+
+            rows[i][j] = current;
+        }
+    }
+
+    let distance = rows[a.len()][b.len()];
+
+    (distance <= threshold).then_some(distance)
+}
+
+/// Formats suggestions as a message suffix, ` Did you mean "a", "b", or "c"?`, or an empty
+/// string when there is nothing to suggest.
+pub fn did_you_mean(suggestions: &[String]) -> String {
+    did_you_mean_with_message(None, suggestions)
+}
+
+/// Same as `did_you_mean`, with some text before the suggestions:
+/// ` Did you mean to use an inline fragment on "A"?`.
+pub fn did_you_mean_with_message(sub_message: Option<&str>, suggestions: &[String]) -> String {
+    let quoted = suggestions
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|s| format!("\"{}\"", s))
+        .collect::<Vec<_>>();
+
+    let list = match quoted.as_slice() {
+        [] => return String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{} or {}", first, second),
+        [rest @ .., last] => format!("{}, or {}", rest.join(", "), last),
+    };
+
+    match sub_message {
+        Some(sub_message) => format!(" Did you mean {} {}?", sub_message, list),
+        None => format!(" Did you mean {}?", list),
+    }
+}
+
+/// Counts the fields of selection sets matching a predicate, following fragment spreads.
+///
+/// A fragment is counted once per spread, but walked only once: its count is memoized, so
+/// nesting spreads of the same fragments doesn't make counting exponential. Fragment cycles
+/// are not followed (they are reported by `NoFragmentsCycle`).
+pub(crate) struct FieldCounter<'a, 'f, P> {
+    known_fragments: &'f HashMap<&'a str, &'a FragmentDefinition>,
+    predicate: P,
+    nested: bool,
+    fragment_counts: HashMap<&'a str, usize>,
+    visiting_fragments: HashSet<&'a str>,
+}
+
+impl<'a, 'f, P: Fn(&Field) -> bool> FieldCounter<'a, 'f, P> {
+    /// Counts the fields of the selection sets of the matching fields too when `nested` is set,
+    /// only the fields of the selection set (and of the fragments it spreads) otherwise.
+    pub fn new(
+        known_fragments: &'f HashMap<&'a str, &'a FragmentDefinition>,
+        nested: bool,
+        predicate: P,
+    ) -> Self {
+        FieldCounter {
+            known_fragments,
+            predicate,
+            nested,
+            fragment_counts: HashMap::new(),
+            visiting_fragments: HashSet::new(),
+        }
+    }
+
+    pub fn count(&mut self, selection_set: &'a SelectionSet) -> usize {
+        selection_set
+            .items
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(field) => {
+                    let nested_count = if self.nested {
+                        self.count(&field.selection_set)
+                    } else {
+                        0
+                    };
+
+                    usize::from((self.predicate)(field)).saturating_add(nested_count)
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.count(&inline_fragment.selection_set)
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    self.fragment_count(&fragment_spread.fragment_name)
+                }
+            })
+            // Fragments spreading each other can expand exponentially: saturate instead of
+            // overflowing, so the limits still apply.
+            .fold(0, usize::saturating_add)
+    }
+
+    /// The matching field at `index`, in the order `count` counts them.
+    pub fn nth(&mut self, selection_set: &'a SelectionSet, mut index: usize) -> Option<&'a Field> {
+        for selection in &selection_set.items {
+            let (nested_selection_set, count) = match selection {
+                Selection::Field(field) => {
+                    if (self.predicate)(field) {
+                        if index == 0 {
+                            return Some(field);
+                        }
+
+                        index -= 1;
+                    }
+
+                    if !self.nested {
+                        continue;
+                    }
+
+                    (&field.selection_set, self.count(&field.selection_set))
+                }
+                Selection::InlineFragment(inline_fragment) => (
+                    &inline_fragment.selection_set,
+                    self.count(&inline_fragment.selection_set),
+                ),
+                Selection::FragmentSpread(fragment_spread) => {
+                    let fragment_name = fragment_spread.fragment_name.as_str();
+                    let count = self.fragment_count(fragment_name);
+
+                    match self.known_fragments.get(fragment_name) {
+                        Some(fragment) => (&fragment.selection_set, count),
+                        None => continue,
+                    }
+                }
+            };
+
+            if index < count {
+                return self.nth(nested_selection_set, index);
+            }
+
+            index -= count;
+        }
+
+        None
+    }
+
+    fn fragment_count(&mut self, fragment_name: &'a str) -> usize {
+        if let Some(count) = self.fragment_counts.get(fragment_name) {
+            return *count;
+        }
+
+        let Some(fragment) = self.known_fragments.get(fragment_name) else {
+            return 0;
+        };
+
+        if !self.visiting_fragments.insert(fragment_name) {
+            return 0;
+        }
+
+        let count = self.count(&fragment.selection_set);
+        self.visiting_fragments.remove(fragment_name);
+        self.fragment_counts.insert(fragment_name, count);
+
+        count
+    }
 }
 
 #[test]
@@ -48,12 +498,13 @@ fn serialization_test() {
     let error = ValidationError {
         locations: vec![Pos { line: 1, column: 2 }],
         message: "test".to_string(),
-        error_code: "test",
+        error_code: "test".into(),
+        details: ValidationErrorDetails::default(),
     };
     let serialized = serde_json::to_string(&error).unwrap();
     assert_eq!(
         serialized,
-        r#"{"locations":[{"line":1,"column":2}],"message":"test"}"#
+        r#"{"message":"test","locations":[{"line":1,"column":2}],"extensions":{"code":"GRAPHQL_VALIDATION_FAILED","rule":"test"}}"#
     );
 }
 
@@ -62,11 +513,89 @@ fn serialization_test_vec() {
     let error = ValidationError {
         locations: vec![Pos { line: 1, column: 2 }],
         message: "test".to_string(),
-        error_code: "test",
+        error_code: "test".into(),
+        details: ValidationErrorDetails::default(),
     };
     let serialized = serde_json::to_string(&vec![error]).unwrap();
     assert_eq!(
         serialized,
-        r#"[{"locations":[{"line":1,"column":2}],"message":"test"}]"#
+        r#"[{"message":"test","locations":[{"line":1,"column":2}],"extensions":{"code":"GRAPHQL_VALIDATION_FAILED","rule":"test"}}]"#
+    );
+}
+
+#[test]
+fn serialization_test_with_details() {
+    let error = ValidationError {
+        locations: vec![Pos { line: 3, column: 5 }],
+        message: "Unknown argument \"foo\" on field \"Query.user\".".to_string(),
+        error_code: ValidationRuleCode::KnownArgumentNames,
+        details: ValidationErrorDetails {
+            type_name: Some("Query".to_string()),
+            field_name: Some("user".to_string()),
+            argument_name: Some("foo".to_string()),
+            ..Default::default()
+        },
+    };
+    let serialized = serde_json::to_string(&error).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"message":"Unknown argument \"foo\" on field \"Query.user\".","locations":[{"line":3,"column":5}],"extensions":{"code":"GRAPHQL_VALIDATION_FAILED","rule":"KnownArgumentNames","typeName":"Query","fieldName":"user","argumentName":"foo"}}"#
+    );
+}
+
+#[test]
+fn rule_codes_round_trip() {
+    for code in [
+        ValidationRuleCode::UniqueOperationNames,
+        ValidationRuleCode::OverlappingFieldsCanBeMerged,
+        ValidationRuleCode::UniqueDirectivesPerLocation,
+        ValidationRuleCode::Custom("MyRule"),
+    ] {
+        assert_eq!(ValidationRuleCode::from(code.as_str()), code);
+        assert_eq!(code, code.as_str());
+    }
+}
+
+#[test]
+fn suggestion_list_ranks_by_distance() {
+    assert_eq!(suggestion_list("", ["a"]), vec!["a"]);
+    assert_eq!(
+        suggestion_list("a", Vec::<String>::new()),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        suggestion_list("GraphQl", ["graphics", "SQL", "GraphQL", "quarks"]),
+        vec!["GraphQL", "graphics"]
+    );
+    assert_eq!(
+        suggestion_list("abc", ["a", "ab", "abc"]),
+        vec!["abc", "ab", "a"]
+    );
+    assert_eq!(
+        suggestion_list("a", ["az", "ax", "ay"]),
+        vec!["ax", "ay", "az"]
+    );
+    assert_eq!(suggestion_list("ab", ["ba"]), vec!["ba"]);
+    assert_eq!(suggestion_list("aaaa", ["aabb"]), vec!["aabb"]);
+    assert_eq!(suggestion_list("aaaa", ["abbb"]), Vec::<String>::new());
+}
+
+#[test]
+fn did_you_mean_formatting() {
+    let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(did_you_mean(&[]), "");
+    assert_eq!(did_you_mean(&names(&["a"])), " Did you mean \"a\"?");
+    assert_eq!(
+        did_you_mean(&names(&["a", "b"])),
+        " Did you mean \"a\" or \"b\"?"
+    );
+    assert_eq!(
+        did_you_mean(&names(&["a", "b", "c", "d", "e", "f"])),
+        " Did you mean \"a\", \"b\", \"c\", \"d\", or \"e\"?"
+    );
+    assert_eq!(
+        did_you_mean_with_message(Some("to use an inline fragment on"), &names(&["A"])),
+        " Did you mean to use an inline fragment on \"A\"?"
     );
 }
@@ -1,29 +1,112 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
 use super::{
+    composite_visitor::CompositeVisitor,
     rules::ValidationRule,
-    utils::{ValidationError, ValidationErrorContext},
+    utils::{ValidationError, ValidationRuleCode},
 };
 
 use crate::{
-    ast::OperationVisitorContext,
+    ast::{prepared_schema::fingerprint, visit_document, OperationVisitorContext, PreparedSchema},
     static_graphql::{query, schema},
 };
 
+/// How the errors of a rule are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The operation is invalid. This is the severity of rules unless configured otherwise.
+    #[default]
+    Error,
+    /// Reported next to the errors by `validate_with_report`, without making the operation
+    /// invalid.
+    Warning,
+    /// The rule doesn't run.
+    Off,
+}
+
 pub struct ValidationPlan {
+    /// The rules, in order. `fingerprint` is cached: after it was computed, change the rules
+    /// through `add_rule` or `rules_mut` rather than this field, so it's computed again.
     pub rules: Vec<Box<dyn ValidationRule>>,
+    severities: HashMap<ValidationRuleCode, Severity>,
+    fingerprint: OnceLock<u64>,
 }
 
 impl ValidationPlan {
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self::from(vec![])
     }
 
     pub fn from(rules: Vec<Box<dyn ValidationRule>>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            severities: HashMap::new(),
+            fingerprint: OnceLock::new(),
+        }
+    }
+
+    /// The rules of this plan, to change them after `fingerprint` was computed.
+    pub fn rules_mut(&mut self) -> &mut Vec<Box<dyn ValidationRule>> {
+        self.fingerprint = OnceLock::new();
+        &mut self.rules
     }
 
     pub fn add_rule(&mut self, rule: Box<dyn ValidationRule>) {
+        self.fingerprint = OnceLock::new();
         self.rules.push(rule);
     }
+
+    /// Adds a rule and sets the severity of its error code.
+    pub fn add_rule_with_severity(&mut self, rule: Box<dyn ValidationRule>, severity: Severity) {
+        self.set_severity(rule.error_code(), severity);
+        self.add_rule(rule);
+    }
+
+    /// Sets the severity of the rules reporting `error_code`.
+    pub fn set_severity(&mut self, error_code: &'static str, severity: Severity) {
+        self.fingerprint = OnceLock::new();
+        self.severities.insert(error_code.into(), severity);
+    }
+
+    pub fn severity(&self, error_code: &'static str) -> Severity {
+        self.severity_of(&error_code.into())
+    }
+
+    fn severity_of(&self, error_code: &ValidationRuleCode) -> Severity {
+        self.severities.get(error_code).copied().unwrap_or_default()
+    }
+
+    /// The rules to run for the given severities, in order.
+    fn rules_with_severity<'p>(
+        &'p self,
+        severities: &'p [Severity],
+    ) -> impl Iterator<Item = &'p Box<dyn ValidationRule>> {
+        self.rules
+            .iter()
+            .filter(move |rule| severities.contains(&self.severity(rule.error_code())))
+    }
+
+    /// A hash of the rules of this plan, their options and severities, in order. Computed once
+    /// after the rules and severities are set.
+    pub fn fingerprint(&self) -> u64 {
+        *self.fingerprint.get_or_init(|| {
+            let rules = self
+                .rules
+                .iter()
+                .map(|rule| match self.severity(rule.error_code()) {
+                    Severity::Error => rule.fingerprint(),
+                    severity => format!("{} ({:?})", rule.fingerprint(), severity),
+                })
+                .collect::<Vec<_>>();
+
+            fingerprint(&rules.join("\n"))
+        })
+    }
 }
 
 impl Default for ValidationPlan {
@@ -32,20 +115,192 @@ impl Default for ValidationPlan {
     }
 }
 
+/// Limits on the work done to validate an operation, so that malicious documents can't produce
+/// huge responses or burn CPU.
+///
+/// When a limit is reached, the traversal stops and the errors end with an error (with the
+/// `ValidationAborted` code) explaining why, so the operation is invalid.
+///
+/// Limits only apply to `validate_with_options`, the other validation functions are unlimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// The maximum number of errors (and warnings) reported, like `maxErrors` in graphql-js.
+    /// 100 with `ValidationOptions::default()`.
+    pub max_errors: Option<usize>,
+    /// The maximum number of AST nodes visited: definitions, selections, directives, arguments
+    /// and values. Unlimited by default.
+    pub max_nodes: Option<usize>,
+    /// The maximum duration of the validation. Unlimited by default.
+    pub timeout: Option<Duration>,
+}
+
+impl ValidationOptions {
+    /// Options without any limit.
+    pub fn unlimited() -> Self {
+        ValidationOptions {
+            max_errors: None,
+            max_nodes: None,
+            timeout: None,
+        }
+    }
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            max_errors: Some(100),
+            ..Self::unlimited()
+        }
+    }
+}
+
+/// The outcome of `validate_with_report`: the errors and warnings reported by the rules of a
+/// plan, according to their severities.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Whether the operation is valid, warnings aside.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validates an operation against a schema document.
+///
+/// The schema is indexed for every call: when validating many operations against the same
+/// schema, prepare it once with `PreparedSchema` and use `validate_with_prepared_schema`.
 pub fn validate<'a>(
     schema: &'a schema::Document,
     operation: &'a query::Document,
     validation_plan: &'a ValidationPlan,
 ) -> Vec<ValidationError> {
-    let mut error_collector = ValidationErrorContext::new();
+    validate_with_prepared_schema(&PreparedSchema::new(schema), operation, validation_plan)
+}
+
+/// Only the rules with the `Error` severity run, see `validate_with_report` for warnings, and
+/// `validate_with_options` to limit the errors reported and the work done.
+pub fn validate_with_prepared_schema<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+) -> Vec<ValidationError> {
+    run_rules(
+        schema,
+        operation,
+        validation_plan,
+        &[Severity::Error],
+        ValidationOptions::unlimited(),
+    )
+}
+
+/// Validates an operation, reporting the errors of the rules with the `Warning` severity
+/// apart from the others, so a single plan can both enforce and lint.
+pub fn validate_with_report<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+) -> ValidationReport {
+    validate_with_options(
+        schema,
+        operation,
+        validation_plan,
+        ValidationOptions::unlimited(),
+    )
+}
+
+/// Same as `validate_with_report`, with the limits of `options`.
+pub fn validate_with_options<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+    options: ValidationOptions,
+) -> ValidationReport {
+    let (warnings, errors) = run_rules(
+        schema,
+        operation,
+        validation_plan,
+        &[Severity::Error, Severity::Warning],
+        options,
+    )
+    .into_iter()
+    .partition(|error| validation_plan.severity_of(&error.error_code) == Severity::Warning);
+
+    ValidationReport { errors, warnings }
+}
+
+fn run_rules<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+    severities: &'a [Severity],
+    options: ValidationOptions,
+) -> Vec<ValidationError> {
     let mut validation_context = OperationVisitorContext::new(operation, schema);
+    let mut visitor = CompositeVisitor::new(validation_plan.rules_with_severity(severities))
+        .with_options(options);
 
-    validation_plan
-        .rules
-        .iter()
-        .for_each(|rule| rule.validate(&mut validation_context, &mut error_collector));
+    visit_document(&mut visitor, operation, &mut validation_context, &mut ());
 
-    error_collector.errors
+    visitor.into_errors()
+}
+
+/// Validates an operation like `validate_with_prepared_schema`, with the limits of `options`
+/// like `validate_with_options`, running the rules of the plan concurrently on the current rayon
+/// thread pool (use `ThreadPool::install` to pick another one).
+///
+/// Every rule traverses the operation on its own, so this only pays off for large documents
+/// validated with many rules. Errors are merged in rule order, so the output is the same as the
+/// sequential validation. Only the rules with the `Error` severity run.
+///
+/// The node and time budgets apply to the traversal of each rule. When `max_errors` is reached,
+/// the first errors in rule order are kept, which may include errors that the sequential
+/// validation didn't reach before aborting.
+#[cfg(feature = "parallel")]
+pub fn validate_parallel<'a>(
+    schema: &'a PreparedSchema<'a>,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+    options: ValidationOptions,
+) -> Vec<ValidationError> {
+    use super::composite_visitor::{aborted, TOO_MANY_ERRORS};
+    use rayon::prelude::*;
+
+    let results = validation_plan
+        .rules_with_severity(&[Severity::Error])
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|rule| {
+            let mut validation_context = OperationVisitorContext::new(operation, schema);
+            let mut visitor =
+                CompositeVisitor::new(std::iter::once(rule)).with_options(options.clone());
+
+            visit_document(&mut visitor, operation, &mut validation_context, &mut ());
+
+            visitor.into_parts()
+        })
+        .collect::<Vec<_>>();
+
+    let mut errors = vec![];
+    let mut abort_error = None;
+
+    for (rule_errors, rule_abort_error) in results {
+        errors.extend(rule_errors);
+        abort_error = abort_error.or(rule_abort_error);
+    }
+
+    if let Some(max_errors) = options.max_errors {
+        if errors.len() > max_errors {
+            errors.truncate(max_errors);
+            abort_error = Some(aborted(TOO_MANY_ERRORS.to_string()));
+        }
+    }
+
+    errors.extend(abort_error);
+    errors
 }
 
 #[test]
@@ -169,3 +424,520 @@ fn fragment_loop_through_multiple_frags() {
     ]
     )
 }
+
+#[test]
+fn single_traversal_reports_errors_in_rule_order() {
+    use crate::parser::Pos;
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let operation_ast = crate::parser::parse_query(
+        "
+        query Foo($unused: Int, $atOtherHomes: String) {
+          dog {
+            name(surname: 1)
+            unknownField
+            isHouseTrained(atOtherHomes: $atOtherHomes)
+            ...UnknownFragment
+            ... on Cat {
+              meows
+            }
+          }
+        }
+
+        query Bar {
+          dog @onQuery {
+            barkVolume: name
+            barkVolume
+          }
+        }
+
+        fragment unused on Dog {
+          name
+        }
+    ",
+    )
+    .unwrap()
+    .into_static();
+
+    let errors = validate(&schema_ast, &operation_ast, &plan);
+
+    let pos = |line, column| Pos { line, column };
+
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (
+                error.error_code.as_str(),
+                error.message.as_str(),
+                error.locations.clone()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "FieldsOnCorrectType",
+                "Cannot query field \"unknownField\" on type \"Dog\".",
+                vec![pos(5, 13)]
+            ),
+            (
+                "KnownFragmentNames",
+                "Unknown fragment \"UnknownFragment\".",
+                vec![pos(7, 16)]
+            ),
+            (
+                "NoUnusedFragments",
+                "Fragment \"unused\" is never used.",
+                vec![pos(21, 9)]
+            ),
+            (
+                "OverlappingFieldsCanBeMerged",
+                "Fields \"barkVolume\" conflict because \"name\" and \"barkVolume\" are different fields. Use different aliases on the fields to fetch both if this was intentional.",
+                vec![pos(16, 13), pos(17, 13)]
+            ),
+            (
+                "PossibleFragmentSpreads",
+                "Fragment cannot be spread here as objects of type \"Dog\" can never be of type \"Cat\".",
+                vec![pos(8, 17)]
+            ),
+            (
+                "NoUnusedVariables",
+                "Variable \"$unused\" is never used in operation \"Foo\".",
+                vec![pos(2, 19)]
+            ),
+            (
+                "KnownDirectives",
+                "Directive \"@onQuery\" may not be used on FIELD",
+                vec![pos(15, 15)]
+            ),
+            (
+                "VariablesInAllowedPosition",
+                "Variable \"$atOtherHomes\" of type \"String\" used in position expecting type \"Boolean\".",
+                vec![pos(2, 33), pos(6, 13)]
+            ),
+            (
+                "ValuesOfCorrectType",
+                "Expected value of type \"Boolean\", found 1.",
+                vec![pos(4, 13)]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn prepared_schema_is_reused_between_validations() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let prepared_schema = PreparedSchema::new(&schema_ast);
+
+    for operation in [
+        "{ dog { name ...on Pet { name } } }",
+        "{ dog { unknownField doesKnowCommand } }",
+        "query($x: Boolean) { pet { ... on Cat { meows } ...dogFields } } fragment dogFields on Dog { barks @skip(if: $x) }",
+        "{ catOrDog { ... on Dog { name: nickname } ... on Dog { name } } }",
+    ] {
+        let operation_ast = crate::parser::parse_query(operation)
+            .unwrap()
+            .into_static();
+
+        assert_eq!(
+            get_messages(&validate_with_prepared_schema(
+                &prepared_schema,
+                &operation_ast,
+                &plan
+            )),
+            get_messages(&validate(&schema_ast, &operation_ast, &plan)),
+            "{}",
+            operation
+        );
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_validation_matches_validate() {
+    use crate::validation::rules::{default_rules_validation_plan, FieldsOnCorrectType};
+    use crate::validation::test_utils::*;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let prepared_schema = PreparedSchema::new(&schema_ast);
+    let operation_ast = crate::parser::parse_query(
+        "
+        query Foo($unused: Int, $atOtherHomes: String) {
+          dog {
+            name(surname: 1)
+            unknownField
+            isHouseTrained(atOtherHomes: $atOtherHomes)
+            ...UnknownFragment
+            ... on Cat {
+              meows
+            }
+          }
+          unknownRootField
+        }
+
+        query Bar {
+          dog @onQuery {
+            barkVolume: name
+            barkVolume
+          }
+        }
+
+        fragment unused on Dog {
+          name
+        }
+    ",
+    )
+    .unwrap()
+    .into_static();
+
+    let summary = |errors: Vec<ValidationError>| {
+        errors
+            .into_iter()
+            .map(|error| (error.error_code, error.message, error.locations))
+            .collect::<Vec<_>>()
+    };
+    let expected = summary(validate(&schema_ast, &operation_ast, &plan));
+    assert!(expected.len() > 5);
+
+    for _ in 0..10 {
+        assert_eq!(
+            summary(validate_parallel(
+                &prepared_schema,
+                &operation_ast,
+                &plan,
+                ValidationOptions::unlimited()
+            )),
+            expected
+        );
+    }
+
+    // Limits are applied like `validate_with_options` does.
+    let plan = create_plan_from_rule(Box::new(FieldsOnCorrectType::new()));
+    let operation_ast = crate::parser::parse_query(&format!(
+        "{{ dog {{ {} }} }}",
+        (0..150)
+            .map(|i| format!("unknown{}", i))
+            .collect::<Vec<_>>()
+            .join(" ")
+    ))
+    .unwrap()
+    .into_static();
+
+    for options in [
+        ValidationOptions::default(),
+        ValidationOptions {
+            max_nodes: Some(5),
+            ..ValidationOptions::unlimited()
+        },
+    ] {
+        assert_eq!(
+            summary(validate_parallel(
+                &prepared_schema,
+                &operation_ast,
+                &plan,
+                options.clone()
+            )),
+            summary(validate_with_options(&prepared_schema, &operation_ast, &plan, options).errors)
+        );
+    }
+}
+
+#[test]
+fn severities_split_errors_and_warnings() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let mut plan = default_rules_validation_plan();
+    plan.set_severity("NoUnusedVariables", Severity::Warning);
+    plan.set_severity("FieldsOnCorrectType", Severity::Off);
+
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let prepared_schema = PreparedSchema::new(&schema_ast);
+    let operation_ast = crate::parser::parse_query(
+        "query Foo($unused: DogCommand) {
+          dog {
+            name(unknownArg: true)
+            unknownField
+          }
+        }",
+    )
+    .unwrap()
+    .into_static();
+
+    let report = validate_with_report(&prepared_schema, &operation_ast, &plan);
+
+    assert!(!report.is_valid());
+    assert_eq!(
+        get_messages(&report.errors),
+        vec!["Unknown argument \"unknownArg\" on field \"Dog.name\"."]
+    );
+    assert_eq!(
+        get_messages(&report.warnings),
+        vec!["Variable \"$unused\" is never used in operation \"Foo\"."]
+    );
+    assert_eq!(
+        get_messages(&validate(&schema_ast, &operation_ast, &plan)),
+        get_messages(&report.errors)
+    );
+    assert_ne!(
+        plan.fingerprint(),
+        default_rules_validation_plan().fingerprint()
+    );
+}
+
+#[test]
+fn fingerprint_follows_changes_to_the_plan() {
+    use crate::validation::rules::{KnownArgumentNames, NoUnusedVariables};
+
+    let mut plan = ValidationPlan::new();
+    plan.add_rule(Box::new(KnownArgumentNames::new()));
+    let single_rule = plan.fingerprint();
+
+    plan.add_rule(Box::new(NoUnusedVariables::new()));
+    let two_rules = plan.fingerprint();
+    assert_ne!(single_rule, two_rules);
+    assert_eq!(plan.fingerprint(), two_rules);
+
+    plan.set_severity("NoUnusedVariables", Severity::Warning);
+    let with_warning = plan.fingerprint();
+    assert_ne!(with_warning, two_rules);
+
+    plan.rules_mut().pop();
+    assert_ne!(plan.fingerprint(), with_warning);
+    assert_eq!(plan.fingerprint(), single_rule);
+}
+
+#[test]
+fn rules_added_with_a_severity() {
+    use crate::validation::rules::{KnownArgumentNames, NoUnusedVariables};
+    use crate::validation::test_utils::*;
+
+    let mut plan = ValidationPlan::new();
+    plan.add_rule(Box::new(KnownArgumentNames::new()));
+    plan.add_rule_with_severity(Box::new(NoUnusedVariables::new()), Severity::Warning);
+
+    assert_eq!(plan.severity("KnownArgumentNames"), Severity::Error);
+    assert_eq!(plan.severity("NoUnusedVariables"), Severity::Warning);
+
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let operation_ast =
+        crate::parser::parse_query("query Foo($unused: DogCommand) { dog { name } }")
+            .unwrap()
+            .into_static();
+    let report = validate_with_report(&PreparedSchema::new(&schema_ast), &operation_ast, &plan);
+
+    assert!(report.is_valid());
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn errors_are_limited() {
+    use crate::validation::rules::FieldsOnCorrectType;
+    use crate::validation::test_utils::*;
+
+    let operation = |fields: usize| {
+        format!(
+            "{{ dog {{ {} }} }}",
+            (0..fields)
+                .map(|i| format!("unknown{}", i))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    };
+    let plan = create_plan_from_rule(Box::new(FieldsOnCorrectType::new()));
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let prepared_schema = PreparedSchema::new(&schema_ast);
+    let validate_with_default_options = |fields: usize| {
+        let operation_ast = crate::parser::parse_query(&operation(fields))
+            .unwrap()
+            .into_static();
+
+        validate_with_options(
+            &prepared_schema,
+            &operation_ast,
+            &plan,
+            ValidationOptions::default(),
+        )
+        .errors
+    };
+
+    let errors = validate_with_default_options(100);
+    assert_eq!(errors.len(), 100);
+
+    let errors = validate_with_default_options(150);
+    assert_eq!(errors.len(), 101);
+    assert_eq!(
+        errors[99].message,
+        "Cannot query field \"unknown99\" on type \"Dog\"."
+    );
+    assert_eq!(
+        errors[100].message,
+        "Too many validation errors, error limit reached. Validation aborted."
+    );
+    assert_eq!(errors[100].error_code, "ValidationAborted");
+    assert!(errors[100].locations.is_empty());
+
+    // Without options, validation is unlimited.
+    let errors = test_operation_with_schema(&operation(150), TEST_SCHEMA, &plan);
+    assert_eq!(errors.len(), 150);
+}
+
+#[test]
+fn node_and_time_budgets_fail_closed() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let prepared_schema = PreparedSchema::new(&schema_ast);
+    let operation_ast = crate::parser::parse_query(
+        "{ dog { name nickname barkVolume mother { name father { name } } } }",
+    )
+    .unwrap()
+    .into_static();
+
+    let report = validate_with_options(
+        &prepared_schema,
+        &operation_ast,
+        &plan,
+        ValidationOptions {
+            max_nodes: Some(5),
+            ..Default::default()
+        },
+    );
+    assert!(!report.is_valid());
+    assert_eq!(
+        get_messages(&report.errors),
+        vec!["Validation node budget of 5 exceeded. Validation aborted."]
+    );
+
+    let report = validate_with_options(
+        &prepared_schema,
+        &operation_ast,
+        &plan,
+        ValidationOptions {
+            max_nodes: Some(100),
+            ..Default::default()
+        },
+    );
+    assert!(report.is_valid());
+
+    let report = validate_with_options(
+        &prepared_schema,
+        &operation_ast,
+        &plan,
+        ValidationOptions {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        get_messages(&report.errors),
+        vec!["Validation time budget of 0ms exceeded. Validation aborted."]
+    );
+}
+
+#[test]
+fn builtins_are_implicit_in_schemas() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema::<String>(
+        "type Query { user(id: ID!): User } type User { name: String, age: Int }",
+    )
+    .unwrap()
+    .into_static();
+    let operation_ast = crate::parser::parse_query::<String>(
+        "query ($id: ID!, $withAge: Boolean!) { user(id: $id) { name age @include(if: $withAge) } }",
+    )
+    .unwrap()
+    .into_static();
+
+    assert_eq!(
+        get_messages(&validate(&schema_ast, &operation_ast, &plan)).len(),
+        0
+    );
+
+    let strict_schema = PreparedSchema::new(&schema_ast).without_builtins();
+
+    let errors = validate_with_prepared_schema(&strict_schema, &operation_ast, &plan);
+    let messages = get_messages(&errors)
+        .into_iter()
+        .filter(|message| {
+            message.starts_with("Unknown type") || message.starts_with("Unknown directive")
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown type \"ID\".",
+            "Unknown type \"Boolean\".",
+            "Unknown directive \"@include\".",
+        ]
+    );
+}
+
+#[test]
+fn introspection_queries_are_validated() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let plan = default_rules_validation_plan();
+    let schema_ast = crate::parser::parse_schema::<String>("type Query { a: Int }")
+        .unwrap()
+        .into_static();
+    let operation_ast = crate::parser::parse_query::<String>(
+        "query {
+          __schema {
+            queryType { name }
+            types { ...FullType }
+            directives { name locations args { ...InputValue } }
+          }
+        }
+        fragment FullType on __Type {
+          kind
+          name
+          fields(includeDeprecated: true) {
+            name
+            args { ...InputValue }
+            type { kind name ofType { kind name } }
+            isDeprecated
+          }
+          enumValues(includeDeprecated: true) { name }
+          possibleTypes { name }
+        }
+        fragment InputValue on __InputValue {
+          name
+          type { name }
+          defaultValue
+        }",
+    )
+    .unwrap()
+    .into_static();
+
+    assert_eq!(
+        get_messages(&validate(&schema_ast, &operation_ast, &plan)).len(),
+        0
+    );
+
+    let operation_ast =
+        crate::parser::parse_query::<String>("{ __schema { types } __type { name } }")
+            .unwrap()
+            .into_static();
+
+    assert_eq!(
+        get_messages(&validate(&schema_ast, &operation_ast, &plan)),
+        vec![
+            "Field \"types\" of type \"[__Type!]!\" must have a selection of subfields. Did you mean \"types { ... }\"?",
+            "Field \"__type\" argument \"name\" of type \"String!\" is required, but it was not provided.",
+        ]
+    );
+}
@@ -0,0 +1,635 @@
+use std::fmt::{self, Display};
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Number, Value as JsonValue};
+use serde_with::ser::SerializeAsWrap;
+
+use super::utils::{did_you_mean, suggestion_list, PositionDef};
+use crate::ast::{
+    InputValueHelpers, OperationDefinitionExtension, PreparedSchema, SchemaDocumentExtension,
+    TypeDefinitionExtension, TypeExtension,
+};
+use crate::parser::Pos;
+use crate::static_graphql::query::{OperationDefinition, Type, Value, VariableDefinition};
+use crate::static_graphql::schema::{EnumType, InputObjectType, TypeDefinition};
+
+/// `extensions.code` of variable coercion errors, as in GraphQL servers.
+pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
+
+/// A step in the path to an invalid value, inside a variable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// An error coercing the value of a variable. `path` starts with the variable name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableValueError {
+    pub locations: Vec<Pos>,
+    pub message: String,
+    pub variable_name: String,
+    pub path: Vec<PathSegment>,
+}
+
+/// Serialized as a GraphQL response error:
+/// `{"message": ..., "locations": [...], "extensions": {"code": "BAD_USER_INPUT", "path": [...]}}`
+impl Serialize for VariableValueError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Extensions<'a> {
+            code: &'static str,
+            variable_name: &'a str,
+            path: &'a [PathSegment],
+        }
+
+        let mut s = serializer.serialize_map(Some(3))?;
+        s.serialize_entry("message", &self.message)?;
+        s.serialize_entry(
+            "locations",
+            &SerializeAsWrap::<Vec<Pos>, Vec<PositionDef>>::new(&self.locations),
+        )?;
+        s.serialize_entry(
+            "extensions",
+            &Extensions {
+                code: BAD_USER_INPUT,
+                variable_name: &self.variable_name,
+                path: &self.path,
+            },
+        )?;
+        s.end()
+    }
+}
+
+struct PrintedPath<'a>(&'a [PathSegment]);
+
+impl Display for PrintedPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if index == 0 => write!(f, "{}", key)?,
+                PathSegment::Key(key) => write!(f, ".{}", key)?,
+                PathSegment::Index(i) => write!(f, "[{}]", i)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Coerces the `variables` of a request for `operation`, following the CoerceVariableValues
+/// algorithm of the spec: default values are applied, null and missing values are checked
+/// against non-null types, and values are coerced to the variable types.
+///
+/// Custom scalars accept any JSON value. Returns all the errors found, in the order of the
+/// variable definitions.
+pub fn coerce_variable_values(
+    schema: &PreparedSchema,
+    operation: &OperationDefinition,
+    variables: &Map<String, JsonValue>,
+) -> Result<Map<String, JsonValue>, Vec<VariableValueError>> {
+    let mut coerced_values = Map::new();
+    let mut errors = vec![];
+
+    for variable_definition in operation.variable_definitions() {
+        let mut coercion = VariableCoercion {
+            schema,
+            variable_definition,
+            path: vec![PathSegment::Key(variable_definition.name.clone())],
+            errors: &mut errors,
+        };
+
+        if let Some(value) = coercion.coerce_variable(variables.get(&variable_definition.name)) {
+            coerced_values.insert(variable_definition.name.clone(), value);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(coerced_values)
+    } else {
+        Err(errors)
+    }
+}
+
+struct VariableCoercion<'a> {
+    schema: &'a PreparedSchema<'a>,
+    variable_definition: &'a VariableDefinition,
+    path: Vec<PathSegment>,
+    errors: &'a mut Vec<VariableValueError>,
+}
+
+impl<'a> VariableCoercion<'a> {
+    fn report(&mut self, message: String) {
+        self.errors.push(VariableValueError {
+            locations: vec![self.variable_definition.position],
+            message,
+            variable_name: self.variable_definition.name.clone(),
+            path: self.path.clone(),
+        });
+    }
+
+    fn report_invalid_value(&mut self, value: &JsonValue, reason: String) {
+        let at = match self.path.len() {
+            1 => String::new(),
+            _ => format!(" at \"{}\"", PrintedPath(&self.path)),
+        };
+
+        self.report(format!(
+            "Variable \"${}\" got invalid value {}{}; {}",
+            self.variable_definition.name, value, at, reason
+        ));
+    }
+
+    fn with_path_segment<T>(
+        &mut self,
+        segment: PathSegment,
+        func: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.path.push(segment);
+        let result = func(self);
+        self.path.pop();
+        result
+    }
+
+    fn coerce_variable(&mut self, value: Option<&JsonValue>) -> Option<JsonValue> {
+        let name = &self.variable_definition.name;
+        let var_type = &self.variable_definition.var_type;
+
+        if !self.is_input_type(var_type.inner_type()) {
+            self.report(format!(
+                "Variable \"${}\" expected value of type \"{}\" which cannot be used as an input type.",
+                name, var_type
+            ));
+            return None;
+        }
+
+        match value {
+            None => {
+                if let Some(default_value) = &self.variable_definition.default_value {
+                    Some(value_to_json(default_value))
+                } else {
+                    if var_type.is_non_null() {
+                        self.report(format!(
+                            "Variable \"${}\" of required type \"{}\" was not provided.",
+                            name, var_type
+                        ));
+                    }
+
+                    None
+                }
+            }
+            Some(JsonValue::Null) if var_type.is_non_null() => {
+                self.report(format!(
+                    "Variable \"${}\" of non-null type \"{}\" must not be null.",
+                    name, var_type
+                ));
+                None
+            }
+            Some(value) => self.coerce_value(value, var_type),
+        }
+    }
+
+    fn is_input_type(&self, type_name: &str) -> bool {
+        is_builtin_scalar(type_name)
+            || matches!(
+                self.schema.type_by_name(type_name),
+                Some(
+                    TypeDefinition::Scalar(_)
+                        | TypeDefinition::Enum(_)
+                        | TypeDefinition::InputObject(_)
+                )
+            )
+    }
+
+    fn coerce_value(&mut self, value: &JsonValue, value_type: &Type) -> Option<JsonValue> {
+        match value_type {
+            Type::NonNullType(inner_type) => {
+                if value.is_null() {
+                    self.report_invalid_value(
+                        value,
+                        format!(
+                            "Expected non-nullable type \"{}\" not to be null.",
+                            value_type
+                        ),
+                    );
+                    return None;
+                }
+
+                self.coerce_value(value, inner_type)
+            }
+            _ if value.is_null() => Some(JsonValue::Null),
+            Type::ListType(item_type) => match value {
+                JsonValue::Array(items) => {
+                    let mut coerced_items = Vec::with_capacity(items.len());
+                    let mut is_valid = true;
+
+                    for (index, item) in items.iter().enumerate() {
+                        match self.with_path_segment(PathSegment::Index(index), |coercion| {
+                            coercion.coerce_value(item, item_type)
+                        }) {
+                            Some(coerced_item) => coerced_items.push(coerced_item),
+                            None => is_valid = false,
+                        }
+                    }
+
+                    is_valid.then_some(JsonValue::Array(coerced_items))
+                }
+                // Input coercion of lists: a single value is a list of one item.
+                value => Some(JsonValue::Array(vec![self.coerce_value(value, item_type)?])),
+            },
+            Type::NamedType(type_name) => self.coerce_named_value(value, type_name),
+        }
+    }
+
+    fn coerce_named_value(&mut self, value: &JsonValue, type_name: &str) -> Option<JsonValue> {
+        if is_builtin_scalar(type_name) {
+            return match coerce_builtin_scalar(value, type_name) {
+                Ok(coerced) => Some(coerced),
+                Err(reason) => {
+                    self.report_invalid_value(value, reason);
+                    None
+                }
+            };
+        }
+
+        match self.schema.type_by_name(type_name) {
+            Some(TypeDefinition::Scalar(_)) => Some(value.clone()),
+            Some(TypeDefinition::Enum(enum_type)) => match coerce_enum(value, enum_type) {
+                Ok(coerced) => Some(coerced),
+                Err(reason) => {
+                    self.report_invalid_value(value, reason);
+                    None
+                }
+            },
+            Some(input_type @ TypeDefinition::InputObject(input_object)) => {
+                self.coerce_input_object(value, input_object, input_type.is_one_of_input_type())
+            }
+            _ => {
+                self.report_invalid_value(value, format!("Unknown type \"{}\".", type_name));
+                None
+            }
+        }
+    }
+
+    fn coerce_input_object(
+        &mut self,
+        value: &JsonValue,
+        input_object: &InputObjectType,
+        is_one_of: bool,
+    ) -> Option<JsonValue> {
+        let JsonValue::Object(fields) = value else {
+            self.report_invalid_value(
+                value,
+                format!("Expected type \"{}\" to be an object.", input_object.name),
+            );
+            return None;
+        };
+
+        let mut coerced_fields = Map::new();
+        let mut is_valid = true;
+
+        for field in &input_object.fields {
+            match fields.get(&field.name) {
+                Some(field_value) => {
+                    match self.with_path_segment(PathSegment::Key(field.name.clone()), |coercion| {
+                        coercion.coerce_value(field_value, &field.value_type)
+                    }) {
+                        Some(coerced) => {
+                            coerced_fields.insert(field.name.clone(), coerced);
+                        }
+                        None => is_valid = false,
+                    }
+                }
+                None => {
+                    if let Some(default_value) = &field.default_value {
+                        coerced_fields.insert(field.name.clone(), value_to_json(default_value));
+                    } else if field.is_required() {
+                        is_valid = false;
+                        self.report_invalid_value(
+                            value,
+                            format!(
+                                "Field \"{}\" of required type \"{}\" was not provided.",
+                                field.name, field.value_type
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for field_name in fields.keys() {
+            if !input_object.fields.iter().any(|f| &f.name == field_name) {
+                is_valid = false;
+                let suggestions =
+                    suggestion_list(field_name, input_object.fields.iter().map(|f| &f.name));
+                self.report_invalid_value(
+                    value,
+                    format!(
+                        "Field \"{}\" is not defined by type \"{}\".{}",
+                        field_name,
+                        input_object.name,
+                        did_you_mean(&suggestions)
+                    ),
+                );
+            }
+        }
+
+        if is_valid && is_one_of {
+            if coerced_fields.len() != 1 {
+                is_valid = false;
+                self.report_invalid_value(
+                    value,
+                    format!(
+                        "Exactly one key must be specified for OneOf type \"{}\".",
+                        input_object.name
+                    ),
+                );
+            } else if let Some((field_name, JsonValue::Null)) = coerced_fields.iter().next() {
+                is_valid = false;
+                self.with_path_segment(PathSegment::Key(field_name.clone()), |coercion| {
+                    coercion.report_invalid_value(
+                        &JsonValue::Null,
+                        format!("Field \"{}\" must be non-null.", field_name),
+                    )
+                });
+            }
+        }
+
+        is_valid.then_some(JsonValue::Object(coerced_fields))
+    }
+}
+
+fn is_builtin_scalar(type_name: &str) -> bool {
+    matches!(type_name, "String" | "Int" | "Float" | "Boolean" | "ID")
+}
+
+fn coerce_builtin_scalar(value: &JsonValue, type_name: &str) -> Result<JsonValue, String> {
+    match (type_name, value) {
+        ("String", JsonValue::String(_)) | ("Boolean", JsonValue::Bool(_)) => Ok(value.clone()),
+        ("String", _) => Err(format!(
+            "String cannot represent a non string value: {}",
+            value
+        )),
+        ("Boolean", _) => Err(format!(
+            "Boolean cannot represent a non boolean value: {}",
+            value
+        )),
+        ("Int", JsonValue::Number(number)) => {
+            let integer = number
+                .as_i64()
+                .or_else(|| {
+                    number
+                        .as_f64()
+                        .filter(|f| f.fract() == 0.0)
+                        .map(|f| f as i64)
+                })
+                .ok_or_else(|| format!("Int cannot represent non-integer value: {}", value))?;
+
+            i32::try_from(integer)
+                .map(|integer| JsonValue::Number(integer.into()))
+                .map_err(|_| {
+                    format!(
+                        "Int cannot represent non 32-bit signed integer value: {}",
+                        value
+                    )
+                })
+        }
+        ("Int", _) => Err(format!("Int cannot represent non-integer value: {}", value)),
+        ("Float", JsonValue::Number(number)) => number
+            .as_f64()
+            .and_then(Number::from_f64)
+            .map(JsonValue::Number)
+            .ok_or_else(|| format!("Float cannot represent non numeric value: {}", value)),
+        ("Float", _) => Err(format!(
+            "Float cannot represent non numeric value: {}",
+            value
+        )),
+        ("ID", JsonValue::String(_)) => Ok(value.clone()),
+        ("ID", JsonValue::Number(number)) if number.is_i64() || number.is_u64() => {
+            Ok(JsonValue::String(number.to_string()))
+        }
+        ("ID", _) => Err(format!("ID cannot represent value: {}", value)),
+        _ => unreachable!("{} is not a built-in scalar", type_name),
+    }
+}
+
+fn coerce_enum(value: &JsonValue, enum_type: &EnumType) -> Result<JsonValue, String> {
+    let JsonValue::String(name) = value else {
+        return Err(format!(
+            "Enum \"{}\" cannot represent non-string value: {}.",
+            enum_type.name, value
+        ));
+    };
+
+    if enum_type.values.iter().any(|v| &v.name == name) {
+        return Ok(value.clone());
+    }
+
+    let suggestions = suggestion_list(name, enum_type.values.iter().map(|v| &v.name));
+
+    Err(format!(
+        "Value \"{}\" does not exist in \"{}\" enum.{}",
+        name,
+        enum_type.name,
+        did_you_mean(&suggestions)
+    ))
+}
+
+/// Converts a constant GraphQL value, like a default value, to JSON. Variables become `null`.
+pub fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Variable(_) | Value::Null => JsonValue::Null,
+        Value::Int(number) => number
+            .as_i64()
+            .map(JsonValue::from)
+            .unwrap_or(JsonValue::Null),
+        Value::Float(float) => Number::from_f64(*float)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null),
+        Value::String(string) => JsonValue::String(string.clone()),
+        Value::Boolean(boolean) => JsonValue::Bool(*boolean),
+        Value::Enum(name) => JsonValue::String(name.clone()),
+        Value::List(items) => JsonValue::Array(items.iter().map(value_to_json).collect()),
+        Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+fn coerce_test_variables(operation: &str, variables: JsonValue) -> Result<JsonValue, Vec<String>> {
+    use crate::static_graphql::query::Definition;
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    let schema = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>(TEST_SCHEMA)
+            .unwrap()
+            .into_static(),
+    );
+    let document = crate::parser::parse_query::<String>(operation)
+        .unwrap()
+        .into_static();
+    let operation = document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        })
+        .unwrap();
+
+    coerce_variable_values(&schema, operation, variables.as_object().unwrap())
+        .map(JsonValue::Object)
+        .map_err(|errors| errors.into_iter().map(|e| e.message).collect())
+}
+
+#[test]
+fn coerces_valid_variables() {
+    use serde_json::json;
+
+    assert_eq!(
+        coerce_test_variables(
+            "query ($id: ID!, $size: Int, $ratio: Float, $flag: Boolean = true, $unset: String, $command: DogCommand) { dog { name } }",
+            json!({ "id": 4, "size": 2.0, "ratio": 1, "command": "SIT" })
+        ),
+        Ok(json!({ "id": "4", "size": 2, "ratio": 1.0, "flag": true, "command": "SIT" }))
+    );
+}
+
+#[test]
+fn coerces_lists_and_input_objects() {
+    use serde_json::json;
+
+    assert_eq!(
+        coerce_test_variables(
+            "query ($ints: [Int], $nested: [[String!]], $input: ComplexInput) { dog { name } }",
+            json!({
+                "ints": 1,
+                "nested": [["a"], null],
+                "input": { "requiredField": true, "stringListField": "one" }
+            })
+        ),
+        Ok(json!({
+            "ints": [1],
+            "nested": [["a"], null],
+            "input": {
+                "requiredField": true,
+                "nonNullField": false,
+                "stringListField": ["one"]
+            }
+        }))
+    );
+}
+
+#[test]
+fn reports_missing_and_null_variables() {
+    use serde_json::json;
+
+    assert_eq!(
+        coerce_test_variables(
+            "query ($a: Int!, $b: String!, $c: Int) { dog { name } }",
+            json!({ "b": null, "c": null })
+        ),
+        Err(vec![
+            "Variable \"$a\" of required type \"Int!\" was not provided.".to_string(),
+            "Variable \"$b\" of non-null type \"String!\" must not be null.".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn reports_invalid_values_with_paths() {
+    use serde_json::json;
+
+    assert_eq!(
+        coerce_test_variables(
+            "query ($ints: [Int!], $input: ComplexInput, $command: DogCommand, $dog: Dog) { dog { name } }",
+            json!({
+                "ints": [1, null, "3", 3000000000i64],
+                "input": { "intField": 1.5, "requirdField": true },
+                "command": "SITT",
+                "dog": {}
+            })
+        ),
+        Err(vec![
+            "Variable \"$ints\" got invalid value null at \"ints[1]\"; Expected non-nullable type \"Int!\" not to be null.".to_string(),
+            "Variable \"$ints\" got invalid value \"3\" at \"ints[2]\"; Int cannot represent non-integer value: \"3\"".to_string(),
+            "Variable \"$ints\" got invalid value 3000000000 at \"ints[3]\"; Int cannot represent non 32-bit signed integer value: 3000000000".to_string(),
+            "Variable \"$input\" got invalid value {\"intField\":1.5,\"requirdField\":true}; Field \"requiredField\" of required type \"Boolean!\" was not provided.".to_string(),
+            "Variable \"$input\" got invalid value 1.5 at \"input.intField\"; Int cannot represent non-integer value: 1.5".to_string(),
+            "Variable \"$input\" got invalid value {\"intField\":1.5,\"requirdField\":true}; Field \"requirdField\" is not defined by type \"ComplexInput\". Did you mean \"requiredField\"?".to_string(),
+            "Variable \"$command\" got invalid value \"SITT\"; Value \"SITT\" does not exist in \"DogCommand\" enum. Did you mean \"SIT\"?".to_string(),
+            "Variable \"$dog\" expected value of type \"Dog\" which cannot be used as an input type.".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn coerces_one_of_input_objects() {
+    use serde_json::json;
+
+    assert_eq!(
+        coerce_test_variables(
+            "query ($a: OneOfInput, $b: OneOfInput) { dog { name } }",
+            json!({ "a": { "intField": 1 }, "b": null })
+        ),
+        Ok(json!({ "a": { "intField": 1 }, "b": null }))
+    );
+    assert_eq!(
+        coerce_test_variables(
+            "query ($a: OneOfInput, $b: OneOfInput, $c: OneOfInput) { dog { name } }",
+            json!({
+                "a": { "intField": 1, "stringField": "one" },
+                "b": {},
+                "c": { "stringField": null }
+            })
+        ),
+        Err(vec![
+            "Variable \"$a\" got invalid value {\"intField\":1,\"stringField\":\"one\"}; Exactly one key must be specified for OneOf type \"OneOfInput\".".to_string(),
+            "Variable \"$b\" got invalid value {}; Exactly one key must be specified for OneOf type \"OneOfInput\".".to_string(),
+            "Variable \"$c\" got invalid value null at \"c.stringField\"; Field \"stringField\" must be non-null.".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn errors_are_serialized_with_paths() {
+    use crate::static_graphql::query::Definition;
+    use serde_json::json;
+
+    let schema = PreparedSchema::from_document(
+        crate::parser::parse_schema::<String>(crate::validation::test_utils::TEST_SCHEMA)
+            .unwrap()
+            .into_static(),
+    );
+    let document = crate::parser::parse_query::<String>("query ($ints: [Int]) { dog { name } }")
+        .unwrap()
+        .into_static();
+    let Some(Definition::Operation(operation)) = document.definitions.first() else {
+        unreachable!()
+    };
+    let errors = coerce_variable_values(
+        &schema,
+        operation,
+        json!({ "ints": [1, true] }).as_object().unwrap(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        serde_json::to_value(&errors).unwrap(),
+        json!([{
+            "message": "Variable \"$ints\" got invalid value true at \"ints[1]\"; Int cannot represent non-integer value: true",
+            "locations": [{ "line": 1, "column": 8 }],
+            "extensions": { "code": "BAD_USER_INPUT", "variableName": "ints", "path": ["ints", 1] }
+        }])
+    );
+}